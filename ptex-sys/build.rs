@@ -26,11 +26,13 @@ fn main() -> Result<()> {
     CFG.exported_header_dirs.extend(include_paths);
 
     cxx_build::bridge("src/lib.rs")
+        .file("src/ptex-sys.cpp")
         .flag_if_supported("-fpermissive")
         .compile("ptex-sys");
 
     println!("cargo:rerun-if-changed=src/lib.rs");
     println!("cargo:rerun-if-changed=src/ptex-sys.h");
+    println!("cargo:rerun-if-changed=src/ptex-sys.cpp");
 
     Ok(())
 }
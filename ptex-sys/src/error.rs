@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+/// Custom error handler used by a PtexCache.
+///
+/// Ptex reports errors that occur while reading files through the error handler.
+/// Error handlers are shared by all threads that read from a cache.
+pub trait ErrorHandler: Send + Sync {
+    /// Report an error message.
+    fn report_error(&self, message: &str);
+}
+
+impl<T: ErrorHandler + ?Sized> ErrorHandler for Arc<T> {
+    fn report_error(&self, message: &str) {
+        (**self).report_error(message);
+    }
+}
+
+/// Boxed ErrorHandler trait object passed to C++.
+pub struct ErrorHandlerBox(Box<dyn ErrorHandler>);
+
+impl ErrorHandlerBox {
+    /// Box an ErrorHandler so that it can be passed to `ffi::ptexerrorhandler_create()`.
+    pub fn new(handler: Box<dyn ErrorHandler>) -> Box<Self> {
+        Box::new(Self(handler))
+    }
+}
+
+/// Forward an error message to the ErrorHandler.
+pub(crate) fn error_handler_report(handler: &ErrorHandlerBox, message: &[u8]) {
+    let message = String::from_utf8_lossy(message);
+    handler.0.report_error(message.trim_end());
+}
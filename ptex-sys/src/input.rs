@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+/// A stream of bytes opened by an [`InputHandler`].
+pub trait InputStream: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> InputStream for T {}
+
+/// Custom file input handler used by a PtexCache.
+///
+/// Input handlers are shared by all threads that read from a cache. Each stream
+/// returned by `open()` is only accessed by one thread at a time.
+pub trait InputHandler: Send + Sync {
+    /// Open a file for reading.
+    fn open(&self, path: &str) -> std::io::Result<Box<dyn InputStream>>;
+}
+
+impl<T: InputHandler + ?Sized> InputHandler for Arc<T> {
+    fn open(&self, path: &str) -> std::io::Result<Box<dyn InputStream>> {
+        (**self).open(path)
+    }
+}
+
+/// Boxed InputHandler trait object passed to C++.
+pub struct InputHandlerBox(Box<dyn InputHandler>);
+
+impl InputHandlerBox {
+    /// Box an InputHandler so that it can be passed to `ffi::ptexinputhandler_create()`.
+    pub fn new(handler: Box<dyn InputHandler>) -> Box<Self> {
+        Box::new(Self(handler))
+    }
+}
+
+thread_local! {
+    /// Error message for the most recent failed call on the current thread.
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Record the error message returned by lastError().
+fn set_last_error(error: impl ToString) {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = error.to_string());
+}
+
+/// Open a file and return its handle, or zero on failure.
+pub(crate) fn input_handler_open(handler: &InputHandlerBox, path: &[u8]) -> usize {
+    let path = String::from_utf8_lossy(path);
+    match handler.0.open(&path) {
+        Ok(stream) => Box::into_raw(Box::new(stream)) as usize,
+        Err(err) => {
+            set_last_error(format!("{path}: {err}"));
+            0
+        }
+    }
+}
+
+/// Seek to an absolute byte position. Returns false on failure.
+///
+/// # Safety
+/// The handle must have been returned by `input_handler_open()` and not yet closed.
+pub(crate) unsafe fn input_handler_seek(
+    _handler: &InputHandlerBox,
+    handle: usize,
+    pos: i64,
+) -> bool {
+    let stream = &mut *(handle as *mut Box<dyn InputStream>);
+    let result = u64::try_from(pos)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+        .and_then(|pos| stream.seek(SeekFrom::Start(pos)));
+    match result {
+        Ok(_) => true,
+        Err(err) => {
+            set_last_error(err);
+            false
+        }
+    }
+}
+
+/// Fill the buffer and return the number of bytes read.
+/// Fewer bytes are returned only when the end of the stream is reached or on failure.
+///
+/// # Safety
+/// The handle must have been returned by `input_handler_open()` and not yet closed.
+pub(crate) unsafe fn input_handler_read(
+    _handler: &InputHandlerBox,
+    handle: usize,
+    buffer: &mut [u8],
+) -> usize {
+    let stream = &mut *(handle as *mut Box<dyn InputStream>);
+    let mut count = 0;
+    while count < buffer.len() {
+        match stream.read(&mut buffer[count..]) {
+            Ok(0) => break,
+            Ok(size) => count += size,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => {
+                set_last_error(err);
+                break;
+            }
+        }
+    }
+    count
+}
+
+/// Close a file handle. Returns true on success.
+///
+/// # Safety
+/// The handle must have been returned by `input_handler_open()` and must not be used again.
+pub(crate) unsafe fn input_handler_close(_handler: &InputHandlerBox, handle: usize) -> bool {
+    if handle != 0 {
+        drop(Box::from_raw(handle as *mut Box<dyn InputStream>));
+    }
+    true
+}
+
+/// Return the error message for the most recent failed call on the current thread.
+pub(crate) fn input_handler_last_error() -> String {
+    LAST_ERROR.with(|last_error| last_error.borrow().clone())
+}
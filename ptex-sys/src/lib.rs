@@ -67,6 +67,36 @@ pub mod ffi {
         Left,
     }
 
+    /// Filter types supported by PtexFilter.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    enum FilterType {
+        /// Point-sampled (no filtering).
+        #[cxx_name = "f_point"]
+        Point,
+        /// Bi-linear interpolation.
+        #[cxx_name = "f_bilinear"]
+        Bilinear,
+        /// Box filter.
+        #[cxx_name = "f_box"]
+        Box,
+        /// Gaussian filter.
+        #[cxx_name = "f_gaussian"]
+        Gaussian,
+        /// General bi-cubic filter (uses sharpness option).
+        #[cxx_name = "f_bicubic"]
+        Bicubic,
+        /// BSpline (equivalent to bi-cubic w/ sharpness=0).
+        #[cxx_name = "f_bspline"]
+        BSpline,
+        /// Catmull-Rom (equivalent to bi-cubic w/ sharpness=1).
+        #[cxx_name = "f_catmullrom"]
+        CatmullRom,
+        /// Mitchell (equivalent to bi-cubic w/ sharpness=2/3).
+        #[cxx_name = "f_mitchell"]
+        Mitchell,
+    }
+
     /// Type of meta data entry.
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     #[repr(u32)]
@@ -129,6 +159,62 @@ pub mod ffi {
         adjfaces: [u32; 4],
     }
 
+    /// Cache statistics returned by ptexcache_get_stats().
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    struct CacheStats {
+        /// Memory currently used by the cache, in bytes.
+        mem_used: u64,
+        /// Peak memory used by the cache, in bytes.
+        peak_mem_used: u64,
+        /// Number of files currently open.
+        files_open: u64,
+        /// Peak number of files open.
+        peak_files_open: u64,
+        /// Number of files accessed.
+        files_accessed: u64,
+        /// Number of times a previously-closed file was reopened.
+        file_reopens: u64,
+        /// Number of data blocks read from disk.
+        block_reads: u64,
+    }
+
+    #[namespace = "Ptex::sys"]
+    extern "Rust" {
+        /// Boxed InputHandler trait object.
+        type InputHandlerBox;
+
+        /// Open a file and return its handle, or zero on failure.
+        fn input_handler_open(handler: &InputHandlerBox, path: &[u8]) -> usize;
+
+        /// Seek to an absolute byte position. Returns false on failure.
+        /// # Safety
+        /// The handle must have been returned by input_handler_open() and not yet closed.
+        unsafe fn input_handler_seek(handler: &InputHandlerBox, handle: usize, pos: i64) -> bool;
+
+        /// Fill the buffer and return the number of bytes read.
+        /// # Safety
+        /// The handle must have been returned by input_handler_open() and not yet closed.
+        unsafe fn input_handler_read(
+            handler: &InputHandlerBox,
+            handle: usize,
+            buffer: &mut [u8],
+        ) -> usize;
+
+        /// Close a file handle. Returns true on success.
+        /// # Safety
+        /// The handle must have been returned by input_handler_open() and must not be used again.
+        unsafe fn input_handler_close(handler: &InputHandlerBox, handle: usize) -> bool;
+
+        /// Return the error message for the most recent failed call on the current thread.
+        fn input_handler_last_error() -> String;
+
+        /// Boxed ErrorHandler trait object.
+        type ErrorHandlerBox;
+
+        /// Forward an error message to the ErrorHandler.
+        fn error_handler_report(handler: &ErrorHandlerBox, message: &[u8]);
+    }
+
     #[namespace = "Ptex::sys"]
     unsafe extern "C++" {
         include!("Ptexture.h");
@@ -143,6 +229,9 @@ pub mod ffi {
         /// How to handle transformation across edges when filtering.
         type EdgeFilterMode;
 
+        /// Filter types supported by PtexFilter.
+        type FilterType;
+
         /// Type of base mesh for which the textures are defined.  A mesh
         /// can be triangle-based (with triangular textures) or quad-based
         /// (with rectangular textures). */
@@ -154,6 +243,15 @@ pub mod ffi {
         /// File-handle and memory cache for reading ptex files.
         type PtexCache;
 
+        /// Custom handler interface for redirecting Ptex error messages.
+        type PtexErrorHandler;
+
+        /// Custom handler interface for intercepting and redirecting Ptex input stream calls.
+        type PtexInputHandler;
+
+        /// Interface for filtered sampling of ptex data.
+        type PtexFilter;
+
         /// Interface for reading metadata from a ptex texture.
         type PtexMetaData;
 
@@ -274,6 +372,19 @@ pub mod ffi {
             premultiply: bool,
         ) -> *mut PtexCache;
 
+        /// Create a cache with the specified limits and custom handlers.
+        /// Either handler may be null to use the default Ptex behavior.
+        /// # Safety
+        /// The value returned must be released using ptexcache_release.
+        /// The handlers must be released after the cache has been released.
+        unsafe fn ptexcache_create_with_handlers(
+            max_files: i32,
+            max_mem: usize,
+            premultiply: bool,
+            input_handler: *mut PtexInputHandler,
+            error_handler: *mut PtexErrorHandler,
+        ) -> *mut PtexCache;
+
         // class PtexCache
 
         /// Release a PtexCache
@@ -294,6 +405,45 @@ pub mod ffi {
         /// This function must be called with a valid PtexCache pointer.
         unsafe fn ptexcache_get_search_path(cache: *mut PtexCache) -> String;
 
+        /// Remove a texture file from the cache.
+        /// If the texture is in use, it will be released when its last reference is released.
+        /// # Safety
+        /// This function must be called with a valid PtexCache pointer.
+        unsafe fn ptexcache_purge(cache: *mut PtexCache, filename: &str);
+
+        /// Remove all texture files from the cache.
+        /// Textures that are in use will be released when their last reference is released.
+        /// # Safety
+        /// This function must be called with a valid PtexCache pointer.
+        unsafe fn ptexcache_purge_all(cache: *mut PtexCache);
+
+        /// Get the cache statistics.
+        /// # Safety
+        /// This function must be called with a valid PtexCache pointer.
+        unsafe fn ptexcache_get_stats(cache: *mut PtexCache, stats: &mut CacheStats);
+
+        // class PtexErrorHandler
+
+        /// Create a PtexErrorHandler that forwards messages to a Rust ErrorHandler.
+        /// The value returned must be released using ptexerrorhandler_release.
+        fn ptexerrorhandler_create(handler: Box<ErrorHandlerBox>) -> *mut PtexErrorHandler;
+
+        /// Release a PtexErrorHandler created by ptexerrorhandler_create.
+        /// # Safety
+        /// The error handler must not be in use by any PtexCache.
+        unsafe fn ptexerrorhandler_release(handler: *mut PtexErrorHandler);
+
+        // class PtexInputHandler
+
+        /// Create a PtexInputHandler that forwards calls to a Rust InputHandler.
+        /// The value returned must be released using ptexinputhandler_release.
+        fn ptexinputhandler_create(handler: Box<InputHandlerBox>) -> *mut PtexInputHandler;
+
+        /// Release a PtexInputHandler created by ptexinputhandler_create.
+        /// # Safety
+        /// The input handler must not be in use by any PtexCache.
+        unsafe fn ptexinputhandler_release(handler: *mut PtexInputHandler);
+
         // class PtexTexture
 
         /// Release a PtexTexture instance.
@@ -381,6 +531,30 @@ pub mod ffi {
             num_channels: i32,
         ) -> f32;
 
+        /// Copy the texel data for a face at its highest resolution into a buffer.
+        /// # Safety
+        /// This function must be called with a valid PtexTexture pointer.
+        /// The buffer must be large enough to hold the face at the specified stride.
+        unsafe fn ptextexture_get_data(
+            texture: *mut PtexTexture,
+            faceid: i32,
+            buffer: *mut u8,
+            stride: i32,
+        );
+
+        /// Copy the texel data for a face at a reduced resolution into a buffer.
+        /// # Safety
+        /// This function must be called with a valid PtexTexture pointer.
+        /// The buffer must be large enough to hold the face at the specified resolution
+        /// and stride.
+        unsafe fn ptextexture_get_data_at_res(
+            texture: *mut PtexTexture,
+            faceid: i32,
+            buffer: *mut u8,
+            stride: i32,
+            res: Res,
+        );
+
         /// Access a texture.  If the specified path was previously accessed
         /// from the cache, then a pointer to the cached texture will be
         /// returned.
@@ -415,6 +589,25 @@ pub mod ffi {
             error_str: *mut CxxString,
         ) -> *mut PtexTexture;
 
+        /// Open a ptex file for reading without a cache.
+        ///
+        /// If the texture could not be opened, null will be returned and
+        /// an error string will be set.
+        ///
+        /// Parameters:
+        /// - filename: File path.
+        /// - error_str: Error string to set if texture could not be opened.
+        /// - premultiply: If true, the texture will be premultiplied by the
+        ///   alpha channel (if any) when read from disk.
+        ///
+        /// # Safety
+        /// The value returned must be released using ptextexture_release.
+        unsafe fn ptextexture_open(
+            filename: &str,
+            error_str: *mut CxxString,
+            premultiply: bool,
+        ) -> *mut PtexTexture;
+
         /// Create a PtexWriter.
         ///
         /// # Safety
@@ -431,10 +624,32 @@ pub mod ffi {
             error_str: *mut CxxString,
         ) -> *mut PtexWriter;
 
+        /// Open an existing texture file for writing.
+        ///
+        /// If the incremental parameter is true, edits will be appended to
+        /// the file as a series of edit blocks. Otherwise the file will be
+        /// rewritten with the edited faces replacing the existing faces.
+        /// The header parameters must match the existing file.
+        ///
+        /// # Safety
+        /// Should not be called outside of the ptex::ffi::sys crate.
+        #[allow(clippy::too_many_arguments)]
+        unsafe fn ptexwriter_edit(
+            filename: &str,
+            incremental: bool,
+            meshtype: MeshType,
+            datatype: DataType,
+            num_channels: i32,
+            alpha_channel: i32,
+            num_faces: i32,
+            genmipmaps: bool,
+            error_str: *mut CxxString,
+        ) -> *mut PtexWriter;
+
         /// Release a PtexWriter.
         ///
         /// # Safety
-        /// Must only be called on pointers returned from ptexwriter_open().
+        /// Must only be called on pointers returned from ptexwriter_open() or ptexwriter_edit().
         unsafe fn ptexwriter_release(writer: *mut PtexWriter);
 
         /// Close an open PtexWriter.
@@ -455,6 +670,18 @@ pub mod ffi {
             stride: i32,
         ) -> bool;
 
+        /// Write constant texture data for a face to a PtexWriter.
+        /// The data must contain a single texel with a value for each channel.
+        ///
+        /// # Safety
+        /// Must only be called on valid PtexWriter pointers.
+        unsafe fn ptexwriter_write_constant_face(
+            writer: *mut PtexWriter,
+            face_id: i32,
+            face_info: &FaceInfo,
+            data: *const u8,
+        ) -> bool;
+
         /// Set the border modes on a PtexWriter.
         ///
         /// # Safety
@@ -479,7 +706,7 @@ pub mod ffi {
         /// # Safety
         /// Must only be called on valid PtexWriter pointers.
         /// the `data` value must be an array of length `count` and appropriate size of the
-        /// `MetaDataType`, or null terminated in the case of `MetaDataType::String`.
+        /// `MetaDataType`. For `MetaDataType::String`, `count` is the length of the string in bytes.
         unsafe fn ptexwriter_write_meta_data(
             writer: *mut PtexWriter,
             key: *const c_char,
@@ -488,6 +715,53 @@ pub mod ffi {
             count: usize,
         ) -> bool;
 
+        // class PtexFilter
+
+        /// Create a PtexFilter for a PtexTexture.
+        /// # Safety
+        /// This function must be called with a valid PtexTexture pointer.
+        /// The PtexTexture must outlive the returned PtexFilter.
+        /// The value returned must be released using ptexfilter_release.
+        unsafe fn ptexfilter_get(
+            texture: *mut PtexTexture,
+            filter_type: FilterType,
+            lerp: bool,
+            sharpness: f32,
+            noedgeblend: bool,
+        ) -> *mut PtexFilter;
+
+        /// Release a PtexFilter.
+        /// # Safety
+        /// This function must be called with a valid PtexFilter pointer.
+        unsafe fn ptexfilter_release(filter: *mut PtexFilter);
+
+        /// Apply the filter to a ptex data file.
+        ///
+        /// The filter region is a parallelogram centered at the given
+        /// (u,v) coordinate with sides defined by two vectors [uw1, vw1]
+        /// and [uw2, vw2].  For an axis-aligned rectangle, the vectors
+        /// are [uw, 0] and [0, vw].
+        ///
+        /// # Safety
+        /// This function must be called with a valid PtexFilter pointer.
+        /// The result buffer must hold at least `num_channels` values.
+        #[allow(clippy::too_many_arguments)]
+        unsafe fn ptexfilter_eval(
+            filter: *mut PtexFilter,
+            result: *mut f32,
+            first_channel: i32,
+            num_channels: i32,
+            face_id: i32,
+            u: f32,
+            v: f32,
+            uw1: f32,
+            vw1: f32,
+            uw2: f32,
+            vw2: f32,
+            width: f32,
+            blur: f32,
+        );
+
         // struct PtexMetaData
 
         /// Get the number of meta data keys from a PtexMetaData pointer.
@@ -648,4 +922,23 @@ impl FaceInfo {
     }
 }
 
+/// Rust implementations of Ptex::PtexInputHandler.
+///
+/// A PtexInputHandler created by `ffi::ptexinputhandler_create()` forwards
+/// its open/seek/read/close calls to a Rust [`InputHandler`].
+mod input;
+use input::{
+    input_handler_close, input_handler_last_error, input_handler_open, input_handler_read,
+    input_handler_seek,
+};
+pub use input::{InputHandler, InputHandlerBox, InputStream};
+
+/// Rust implementations of Ptex::PtexErrorHandler.
+///
+/// A PtexErrorHandler created by `ffi::ptexerrorhandler_create()` forwards
+/// error messages to a Rust [`ErrorHandler`].
+mod error;
+use error::error_handler_report;
+pub use error::{ErrorHandler, ErrorHandlerBox};
+
 pub use ffi::*;
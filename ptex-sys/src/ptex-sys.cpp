@@ -0,0 +1,99 @@
+/// PtexInputHandler and PtexErrorHandler implementations that call into Rust.
+/// The generated lib.rs.h header is required for calling the Rust callbacks
+/// so these definitions cannot live inline in ptex-sys.h.
+#include "ptex-sys/src/lib.rs.h"
+
+#include <cstdint>
+#include <string>
+#include <utility>
+
+namespace Ptex {
+namespace sys {
+
+/// PtexInputHandler that forwards all calls to a Rust InputHandler.
+class RustInputHandler : public PtexInputHandler
+{
+public:
+    explicit RustInputHandler(rust::Box<InputHandlerBox> handler)
+        : m_handler(std::move(handler))
+    {
+    }
+
+    Handle open(const char *path) override
+    {
+        rust::Slice<const std::uint8_t> path_bytes(
+            reinterpret_cast<const std::uint8_t *>(path), std::char_traits<char>::length(path));
+        return reinterpret_cast<Handle>(input_handler_open(*m_handler, path_bytes));
+    }
+
+    void seek(Handle handle, int64_t pos) override
+    {
+        input_handler_seek(*m_handler, reinterpret_cast<std::size_t>(handle), pos);
+    }
+
+    size_t read(void *buffer, size_t size, Handle handle) override
+    {
+        rust::Slice<std::uint8_t> buffer_bytes(static_cast<std::uint8_t *>(buffer), size);
+        return input_handler_read(
+            *m_handler, reinterpret_cast<std::size_t>(handle), buffer_bytes);
+    }
+
+    bool close(Handle handle) override
+    {
+        return input_handler_close(*m_handler, reinterpret_cast<std::size_t>(handle));
+    }
+
+    const char *lastError() override
+    {
+        // The returned pointer must remain valid after this call returns.
+        thread_local std::string last_error;
+        last_error = std::string(input_handler_last_error());
+        return last_error.c_str();
+    }
+
+private:
+    rust::Box<InputHandlerBox> m_handler;
+};
+
+PtexInputHandler *ptexinputhandler_create(rust::Box<InputHandlerBox> handler)
+{
+    return new RustInputHandler(std::move(handler));
+}
+
+void ptexinputhandler_release(PtexInputHandler *handler)
+{
+    delete static_cast<RustInputHandler *>(handler);
+}
+
+/// PtexErrorHandler that forwards all messages to a Rust ErrorHandler.
+class RustErrorHandler : public PtexErrorHandler
+{
+public:
+    explicit RustErrorHandler(rust::Box<ErrorHandlerBox> handler)
+        : m_handler(std::move(handler))
+    {
+    }
+
+    void reportError(const char *error) override
+    {
+        rust::Slice<const std::uint8_t> error_bytes(
+            reinterpret_cast<const std::uint8_t *>(error), std::char_traits<char>::length(error));
+        error_handler_report(*m_handler, error_bytes);
+    }
+
+private:
+    rust::Box<ErrorHandlerBox> m_handler;
+};
+
+PtexErrorHandler *ptexerrorhandler_create(rust::Box<ErrorHandlerBox> handler)
+{
+    return new RustErrorHandler(std::move(handler));
+}
+
+void ptexerrorhandler_release(PtexErrorHandler *handler)
+{
+    delete static_cast<RustErrorHandler *>(handler);
+}
+
+} // namespace sys
+} // namespace Ptex
@@ -16,6 +16,9 @@ namespace sys {
 
 using namespace Ptex;
 
+/// PtexFilter::FilterType is nested inside PtexFilter and must be aliased for cxx.
+using FilterType = PtexFilter::FilterType;
+
 /// Entry point into static PtexWriter::open().
 inline PtexWriter *ptexwriter_open(
     rust::Str filename,
@@ -33,6 +36,23 @@ inline PtexWriter *ptexwriter_open(
         numfaces, *error, genmipmaps);
 }
 
+/// Entry point into static PtexWriter::edit().
+inline PtexWriter *ptexwriter_edit(
+    rust::Str filename,
+    bool incremental,
+    MeshType meshtype,
+    DataType datatype,
+    std::int32_t numchannels,
+    std::int32_t alphachan,
+    std::int32_t numfaces,
+    bool genmipmaps,
+    std::string *error)
+{
+    return PtexWriter::edit(
+        std::string(filename).c_str(), incremental, meshtype, datatype, numchannels,
+        alphachan, numfaces, *error, genmipmaps);
+}
+
 /// Release a PtexWriter instance.
 inline void ptexwriter_release(PtexWriter *writer)
 {
@@ -60,6 +80,16 @@ inline bool ptexwriter_write_face(
     return writer->writeFace(face_id, face_info, (void *)data, stride);
 }
 
+/// Write constant texture data for a face.
+inline bool ptexwriter_write_constant_face(
+    PtexWriter *writer,
+    std::int32_t face_id,
+    const FaceInfo &face_info,
+    const std::uint8_t *data)
+{
+    return writer->writeConstantFace(face_id, face_info, (void *)data);
+}
+
 /// Set border modes for writer
 inline void ptexwriter_set_border_modes(
     PtexWriter *writer, BorderMode u_border_mode, BorderMode v_border_mode)
@@ -84,8 +114,8 @@ inline bool ptexwriter_write_meta_data(
 {
     switch (metadata_kind) {
     case Ptex::mdt_string:
-        // Assume null terminated.
-        writer->writeMeta(key, (char *)data);
+        // Rust strings are not null terminated.
+        writer->writeMeta(key, std::string((const char *)data, count).c_str());
         return true;
     case Ptex::mdt_int8:
         writer->writeMeta(key, (int8_t *)data, count);
@@ -229,6 +259,17 @@ ptexcache_get(PtexCache *cache, rust::Str filename, std::string *error_string)
     return cache->get(std::string(filename).c_str(), *error_string);
 }
 
+/// Create a new PtexCache with custom input and error handlers.
+inline PtexCache *ptexcache_create_with_handlers(
+    int32_t max_files,
+    size_t max_mem,
+    bool premultiply,
+    PtexInputHandler *input_handler,
+    PtexErrorHandler *error_handler)
+{
+    return PtexCache::create(max_files, max_mem, premultiply, input_handler, error_handler);
+}
+
 /// Set the search path on a PtexCache instance.
 inline void ptexcache_set_search_path(PtexCache *cache, rust::Str path)
 {
@@ -241,6 +282,63 @@ inline rust::String ptexcache_get_search_path(PtexCache *cache)
     return rust::String(cache->getSearchPath());
 }
 
+/// Remove a texture file from the PtexCache.
+inline void ptexcache_purge(PtexCache *cache, rust::Str filename)
+{
+    cache->purge(std::string(filename).c_str());
+}
+
+/// Remove all texture files from the PtexCache.
+inline void ptexcache_purge_all(PtexCache *cache)
+{
+    cache->purgeAll();
+}
+
+/// CacheStats is a shared struct that is defined by cxx after this header is included.
+/// The template parameter defers instantiation until CacheStats is a complete type.
+struct CacheStats;
+
+/// Get the PtexCache statistics.
+template <typename Stats = CacheStats>
+inline void ptexcache_get_stats(PtexCache *cache, Stats &stats)
+{
+    PtexCache::Stats cache_stats;
+    cache->getStats(cache_stats);
+    stats.mem_used = cache_stats.memUsed;
+    stats.peak_mem_used = cache_stats.peakMemUsed;
+    stats.files_open = cache_stats.filesOpen;
+    stats.peak_files_open = cache_stats.peakFilesOpen;
+    stats.files_accessed = cache_stats.filesAccessed;
+    stats.file_reopens = cache_stats.fileReopens;
+    stats.block_reads = cache_stats.blockReads;
+}
+
+// class PtexErrorHandler
+
+/// Opaque Rust type defined by cxx in lib.rs.h.
+struct ErrorHandlerBox;
+
+/// Create a PtexErrorHandler that forwards messages to a Rust ErrorHandler.
+/// Defined in ptex-sys.cpp.
+PtexErrorHandler *ptexerrorhandler_create(rust::Box<ErrorHandlerBox> handler);
+
+/// Release a PtexErrorHandler created by ptexerrorhandler_create().
+/// Defined in ptex-sys.cpp.
+void ptexerrorhandler_release(PtexErrorHandler *handler);
+
+// class PtexInputHandler
+
+/// Opaque Rust type defined by cxx in lib.rs.h.
+struct InputHandlerBox;
+
+/// Create a PtexInputHandler that forwards calls to a Rust InputHandler.
+/// Defined in ptex-sys.cpp.
+PtexInputHandler *ptexinputhandler_create(rust::Box<InputHandlerBox> handler);
+
+/// Release a PtexInputHandler created by ptexinputhandler_create().
+/// Defined in ptex-sys.cpp.
+void ptexinputhandler_release(PtexInputHandler *handler);
+
 /// Return true if the FaceInfo instance contains edits.
 inline bool faceinfo_has_edits(FaceInfo *info)
 {
@@ -293,6 +391,13 @@ inline void faceinfo_set_adjacent_faces(
 
 // class PtexTexture
 
+/// Entry point into static PtexTexture::open().
+inline PtexTexture *
+ptextexture_open(rust::Str filename, std::string *error_string, bool premultiply)
+{
+    return PtexTexture::open(std::string(filename).c_str(), *error_string, premultiply);
+}
+
 /// Release a PtexTexture instance.
 inline void ptextexture_release(PtexTexture *texture)
 {
@@ -387,11 +492,76 @@ inline float ptextexture_get_pixel(
     std::int32_t first_channel,
     std::int32_t num_channels)
 {
-    float result;
-    texture->getPixel(faceid, u, v, &result, first_channel, num_channels);
+    // Only the first channel is returned, so only the first channel is read.
+    float result = 0.0f;
+    if (num_channels > 0) {
+        texture->getPixel(faceid, u, v, &result, first_channel, 1);
+    }
     return result;
 }
 
+inline void ptextexture_get_data(
+    PtexTexture *texture,
+    std::int32_t faceid,
+    std::uint8_t *buffer,
+    std::int32_t stride)
+{
+    texture->getData(faceid, (void *)buffer, stride);
+}
+
+inline void ptextexture_get_data_at_res(
+    PtexTexture *texture,
+    std::int32_t faceid,
+    std::uint8_t *buffer,
+    std::int32_t stride,
+    Res res)
+{
+    texture->getData(faceid, (void *)buffer, stride, res);
+}
+
+// class PtexFilter
+
+/// Create a PtexFilter for the specified texture and filter options.
+inline PtexFilter *ptexfilter_get(
+    PtexTexture *texture,
+    FilterType filter_type,
+    bool lerp,
+    float sharpness,
+    bool noedgeblend)
+{
+    PtexFilter::Options options(filter_type, lerp, sharpness, noedgeblend);
+    return PtexFilter::getFilter(texture, options);
+}
+
+/// Release a PtexFilter instance.
+inline void ptexfilter_release(PtexFilter *filter)
+{
+    if (filter) {
+        filter->release();
+    }
+}
+
+/// Evaluate a PtexFilter.
+inline void ptexfilter_eval(
+    PtexFilter *filter,
+    float *result,
+    std::int32_t first_channel,
+    std::int32_t num_channels,
+    std::int32_t face_id,
+    float u,
+    float v,
+    float uw1,
+    float vw1,
+    float uw2,
+    float vw2,
+    float width,
+    float blur)
+{
+    filter->eval(
+        result, first_channel, num_channels, face_id, u, v, uw1, vw1, uw2, vw2, width,
+        blur);
+}
+
 // struct PtexMetaData
 inline std::int32_t ptexmetadata_num_keys(PtexMetaData *metadata)
 {
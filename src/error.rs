@@ -1,4 +1,10 @@
-/// The main Error type returns either error Messages or FileIO errors.
+use crate::sys::ErrorHandler;
+use crate::{DataType, MetaDataType, Res};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::thread::ThreadId;
+
+/// The main Error type returned by the ptex crate.
 #[derive(Debug, Clone, PartialEq, thiserror::Error)]
 pub enum Error {
     /// An error occurred when reading from a Pathbuf.
@@ -8,4 +14,229 @@ pub enum Error {
     /// General error messages.
     #[error("{0:?}")]
     Message(String),
+
+    /// A file could not be found or opened.
+    #[error("{0:?}: not found: {1:?}")]
+    NotFound(std::path::PathBuf, String),
+
+    /// A file has an invalid header or is not a Ptex file.
+    #[error("{0:?}: invalid format: {1:?}")]
+    InvalidFormat(std::path::PathBuf, String),
+
+    /// A buffer's element type does not match the DataType of a texture or writer.
+    #[error("data type mismatch: expected {expected:?}, found {actual:?}")]
+    DataTypeMismatch {
+        /// DataType required by the texture or writer.
+        expected: DataType,
+        /// DataType of the buffer.
+        actual: DataType,
+    },
+
+    /// A buffer is too small for the requested data.
+    #[error("buffer too small: {required} values are required, found {actual}")]
+    BufferTooSmall {
+        /// Number of values required.
+        required: usize,
+        /// Number of values in the buffer.
+        actual: usize,
+    },
+
+    /// A face id is not in the range [0..num_faces-1].
+    #[error("face id {face_id} is out of range for {num_faces} faces")]
+    FaceIdOutOfRange {
+        /// Requested face id.
+        face_id: i32,
+        /// Number of faces in the texture or writer.
+        num_faces: i32,
+    },
+
+    /// A resolution is not available for a face.
+    #[error("resolution {res:?} is not available for face {face_id} with resolution {face_res:?}")]
+    InvalidResolution {
+        /// Face id.
+        face_id: i32,
+        /// Requested resolution.
+        res: Res,
+        /// Stored resolution of the face.
+        face_res: Res,
+    },
+
+    /// A row stride is too small to hold a row or is not a multiple of the value size.
+    #[error("invalid stride {stride} for rows of {row_size} bytes of {value_size}-byte values")]
+    InvalidStride {
+        /// Requested distance between rows, in bytes.
+        stride: i32,
+        /// Size of a row, in bytes.
+        row_size: usize,
+        /// Size of a single value, in bytes.
+        value_size: usize,
+    },
+
+    /// A texel coordinate is outside of a face's resolution.
+    #[error("texel ({u}, {v}) is out of range for face {face_id} with resolution {ures}x{vres}")]
+    TexelOutOfRange {
+        /// Face id.
+        face_id: i32,
+        /// Requested U coordinate.
+        u: i32,
+        /// Requested V coordinate.
+        v: i32,
+        /// U resolution of the face.
+        ures: i32,
+        /// V resolution of the face.
+        vres: i32,
+    },
+
+    /// A channel range is outside of [0..num_channels-1].
+    #[error(
+        "channels [{first_channel}..{first_channel}+{num_channels}) are out of range \
+         for {available} channels"
+    )]
+    ChannelOutOfRange {
+        /// Requested first channel.
+        first_channel: i32,
+        /// Requested number of channels.
+        num_channels: i32,
+        /// Number of channels available.
+        available: i32,
+    },
+
+    /// An error occurred while writing a file.
+    #[error("{0:?}: write failed: {1:?}")]
+    WriteFailed(std::path::PathBuf, String),
+
+    /// Writing a file was cancelled and the partial output was removed.
+    #[error("{0:?}: cancelled")]
+    Cancelled(std::path::PathBuf),
+
+    /// A meta data entry does not have the requested type.
+    #[error("meta data {key:?} has type {actual:?}, not {expected:?}")]
+    MetaDataTypeMismatch {
+        /// Meta data key.
+        key: String,
+        /// Requested meta data type.
+        expected: MetaDataType,
+        /// Stored meta data type.
+        actual: MetaDataType,
+    },
+}
+
+/// Build the error returned when a texture cannot be opened.
+///
+/// Files that do not exist are reported as [`Error::NotFound`] and all other failures
+/// as [`Error::InvalidFormat`]. Callers determine whether the file exists from the
+/// filesystem or input handler that Ptex read it from. The Ptex error string is used
+/// as the message when present, and any messages collected by an [`ErrorCollector`]
+/// are appended to it.
+pub(crate) fn open_error(
+    context: &str,
+    filename: &std::path::Path,
+    not_found: bool,
+    error_str: &str,
+    messages: &[String],
+) -> Error {
+    let mut error_message = error_str.trim_end().to_string();
+    if !messages.is_empty() {
+        if !error_message.is_empty() {
+            error_message.push_str(": ");
+        }
+        error_message.push_str(&messages.join("; "));
+    }
+    if error_message.is_empty() {
+        error_message = format!("ptex: {context}({filename:?}) failed");
+    }
+    if not_found {
+        Error::NotFound(filename.to_path_buf(), error_message)
+    } else {
+        Error::InvalidFormat(filename.to_path_buf(), error_message)
+    }
+}
+
+/// Return true if an I/O result failed because the file does not exist.
+pub(crate) fn is_not_found<T>(result: std::io::Result<T>) -> bool {
+    result.is_err_and(|err| err.kind() == std::io::ErrorKind::NotFound)
+}
+
+/// Error handler that collects Ptex error messages per thread.
+///
+/// Ptex reports errors through its error handler and then returns null or zero-filled
+/// data. Messages are recorded for the thread that reported them while that thread is
+/// running an operation through [`ErrorCollector::collect()`], so that the operation
+/// can return them in an [`Error`]. Messages reported outside of an operation, such as
+/// by threads evaluating filters, are not recorded, so the collector only holds
+/// messages for operations that are in progress.
+/// Messages are also forwarded to an optional user-provided error handler.
+pub(crate) struct ErrorCollector {
+    messages: Mutex<HashMap<ThreadId, Vec<String>>>,
+    handler: Option<Box<dyn ErrorHandler>>,
+}
+
+impl ErrorHandler for ErrorCollector {
+    fn report_error(&self, message: &str) {
+        if let Some(handler) = &self.handler {
+            handler.report_error(message);
+        }
+        if let Some(messages) = self.lock().get_mut(&std::thread::current().id()) {
+            messages.push(message.to_string());
+        }
+    }
+}
+
+impl ErrorCollector {
+    /// Create an ErrorCollector that forwards messages to an optional error handler.
+    pub(crate) fn new(handler: Option<Box<dyn ErrorHandler>>) -> Self {
+        Self {
+            messages: Mutex::default(),
+            handler,
+        }
+    }
+
+    /// Run an operation and return the messages it reported on the current thread.
+    pub(crate) fn collect<R>(&self, operation: impl FnOnce() -> R) -> (R, Vec<String>) {
+        /// Stop recording messages for the current thread, even if the operation panics.
+        struct Scope<'a>(&'a ErrorCollector);
+
+        impl Drop for Scope<'_> {
+            fn drop(&mut self) {
+                self.0.lock().remove(&std::thread::current().id());
+            }
+        }
+
+        self.lock().insert(std::thread::current().id(), Vec::new());
+        let scope = Scope(self);
+        let result = operation();
+        let messages = self
+            .lock()
+            .remove(&std::thread::current().id())
+            .unwrap_or_default();
+        drop(scope);
+
+        (result, messages)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<ThreadId, Vec<String>>> {
+        self.messages.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Run a Ptex operation, collecting its messages when an [`ErrorCollector`] is present.
+pub(crate) fn collect_errors<R>(
+    errors: Option<&ErrorCollector>,
+    operation: impl FnOnce() -> R,
+) -> (R, Vec<String>) {
+    match errors {
+        Some(errors) => errors.collect(operation),
+        None => (operation(), Vec::new()),
+    }
+}
+
+/// Return an error when messages were reported by an operation.
+pub(crate) fn check_messages(context: &str, messages: Vec<String>) -> Result<(), Error> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Message(format!(
+        "{context}: {}",
+        messages.join("; ")
+    )))
 }
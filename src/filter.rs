@@ -0,0 +1,327 @@
+use crate::{sys, Error, FilterType, Texture};
+
+/// Options used to create a [`Filter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterOptions {
+    /// Type of filter.
+    pub filter_type: FilterType,
+    /// Interpolate between mipmap levels.
+    pub lerp: bool,
+    /// Filter sharpness, 0..1 (for general bi-cubic filter only).
+    pub sharpness: f32,
+    /// Disable cross-face filtering. Useful for debugging or rendering on polys.
+    pub noedgeblend: bool,
+}
+
+impl Default for FilterOptions {
+    /// The default options use a box filter.
+    fn default() -> Self {
+        Self {
+            filter_type: FilterType::Box,
+            lerp: false,
+            sharpness: 0.0,
+            noedgeblend: false,
+        }
+    }
+}
+
+impl FilterOptions {
+    /// Create filter options for the specified filter type.
+    pub fn new(filter_type: FilterType) -> Self {
+        Self {
+            filter_type,
+            ..Self::default()
+        }
+    }
+}
+
+/// A single filter lookup: a face, a filter center and a filter footprint.
+///
+/// See [`Filter::eval()`] for a description of each field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterLookup {
+    /// Face index [0..num_faces-1].
+    pub face_id: i32,
+    /// Filter center, U coordinate [0..1].
+    pub u: f32,
+    /// Filter center, V coordinate [0..1].
+    pub v: f32,
+    /// Filter footprint, first vector, U component.
+    pub uw1: f32,
+    /// Filter footprint, first vector, V component.
+    pub vw1: f32,
+    /// Filter footprint, second vector, U component.
+    pub uw2: f32,
+    /// Filter footprint, second vector, V component.
+    pub vw2: f32,
+    /// Scale factor for filter width.
+    pub width: f32,
+    /// Amount to add to filter width [0..1].
+    pub blur: f32,
+}
+
+impl Default for FilterLookup {
+    fn default() -> Self {
+        Self {
+            face_id: 0,
+            u: 0.0,
+            v: 0.0,
+            uw1: 0.0,
+            vw1: 0.0,
+            uw2: 0.0,
+            vw2: 0.0,
+            width: 1.0,
+            blur: 0.0,
+        }
+    }
+}
+
+impl FilterLookup {
+    /// Create a lookup with an axis-aligned footprint of size (uw, vw).
+    pub fn new(face_id: i32, u: f32, v: f32, uw: f32, vw: f32) -> Self {
+        Self {
+            face_id,
+            u,
+            v,
+            uw1: uw,
+            vw2: vw,
+            ..Self::default()
+        }
+    }
+}
+
+/// Interface for filtered sampling of ptex data.
+///
+/// Filters are created from a [`Texture`] and cannot outlive it:
+///
+/// ```compile_fail
+/// let cache = ptex::Cache::new(0, 0, false);
+/// let texture = cache.get("tests/fixtures/test.ptx").unwrap();
+/// let filter = ptex::Filter::new(&texture, &ptex::FilterOptions::default()).unwrap();
+/// drop(texture);
+/// drop(filter);
+/// ```
+///
+/// # Thread safety
+///
+/// Filters hold per-lookup state and are `Send` but not `Sync`. Create one filter
+/// per thread from a shared Texture instead of sharing a filter between threads.
+/// [`Filter::eval_parallel()`] does this automatically.
+pub struct Filter<'a> {
+    ptr: *mut sys::PtexFilter,
+    texture: std::marker::PhantomData<&'a Texture<'a>>,
+}
+
+/// Filters may be moved to another thread but are not safe to share between threads.
+unsafe impl Send for Filter<'_> {}
+
+impl Drop for Filter<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::ptexfilter_release(self.ptr);
+        }
+    }
+}
+
+impl<'a> Filter<'a> {
+    /// Create a filter for the specified texture and options.
+    pub fn new(texture: &'a Texture<'_>, options: &FilterOptions) -> Result<Self, Error> {
+        let ptr = unsafe {
+            sys::ptexfilter_get(
+                texture.ptr,
+                options.filter_type.into(),
+                options.lerp,
+                options.sharpness,
+                options.noedgeblend,
+            )
+        };
+        if ptr.is_null() {
+            return Err(Error::Message(format!(
+                "ptex: Filter::new({:?}) failed",
+                options.filter_type
+            )));
+        }
+
+        Ok(Self {
+            ptr,
+            texture: std::marker::PhantomData,
+        })
+    }
+
+    /// Apply the filter to a ptex data file.
+    ///
+    /// The filter region is a parallelogram centered at the given (u, v) coordinate with
+    /// sides defined by two vectors [uw1, vw1] and [uw2, vw2]. For an axis-aligned rectangle,
+    /// the vectors are [uw, 0] and [0, vw]. See the Ptex documentation on filter footprints
+    /// for more details.
+    ///
+    /// Parameters:
+    /// - result: Buffer to hold filter result. Must hold at least `num_channels` values.
+    /// - first_channel: First channel to evaluate [0..num_channels-1].
+    /// - num_channels: Number of channels to evaluate.
+    /// - face_id: Face index [0..num_faces-1].
+    /// - u, v: Filter center, in face coordinates [0..1].
+    /// - uw1, vw1: Filter footprint, first vector.
+    /// - uw2, vw2: Filter footprint, second vector.
+    /// - width: Scale factor for filter width.
+    /// - blur: Amount to add to filter width [0..1].
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval(
+        &mut self,
+        result: &mut [f32],
+        first_channel: i32,
+        num_channels: i32,
+        face_id: i32,
+        u: f32,
+        v: f32,
+        uw1: f32,
+        vw1: f32,
+        uw2: f32,
+        vw2: f32,
+        width: f32,
+        blur: f32,
+    ) -> Result<(), Error> {
+        if first_channel < 0 || num_channels < 0 {
+            return Err(Error::Message(format!(
+                "ptex: Filter::eval() invalid channels [{first_channel}..{}]",
+                first_channel + num_channels,
+            )));
+        }
+        if result.len() < num_channels as usize {
+            return Err(Error::BufferTooSmall {
+                required: num_channels as usize,
+                actual: result.len(),
+            });
+        }
+        unsafe {
+            sys::ptexfilter_eval(
+                self.ptr,
+                result.as_mut_ptr(),
+                first_channel,
+                num_channels,
+                face_id,
+                u,
+                v,
+                uw1,
+                vw1,
+                uw2,
+                vw2,
+                width,
+                blur,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate a batch of lookups.
+    ///
+    /// The filtered values for `lookups[i]` are stored in
+    /// `results[i * num_channels..(i + 1) * num_channels]`.
+    pub fn eval_batch(
+        &mut self,
+        lookups: &[FilterLookup],
+        first_channel: i32,
+        num_channels: i32,
+        results: &mut [f32],
+    ) -> Result<(), Error> {
+        check_results_len(lookups, num_channels, results)?;
+        if num_channels == 0 {
+            return Ok(());
+        }
+        for (lookup, result) in lookups
+            .iter()
+            .zip(results.chunks_exact_mut(num_channels as usize))
+        {
+            self.eval(
+                result,
+                first_channel,
+                num_channels,
+                lookup.face_id,
+                lookup.u,
+                lookup.v,
+                lookup.uw1,
+                lookup.vw1,
+                lookup.uw2,
+                lookup.vw2,
+                lookup.width,
+                lookup.blur,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate a batch of lookups across multiple threads.
+    ///
+    /// The lookups are split into contiguous chunks and each worker thread evaluates
+    /// its chunk using its own Filter created from the shared Texture. Results are
+    /// stored in the same layout as [`Filter::eval_batch()`].
+    ///
+    /// Parameters:
+    /// - num_threads: Number of worker threads. If zero, the available parallelism is used.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval_parallel(
+        texture: &Texture<'_>,
+        options: &FilterOptions,
+        lookups: &[FilterLookup],
+        first_channel: i32,
+        num_channels: i32,
+        results: &mut [f32],
+        num_threads: usize,
+    ) -> Result<(), Error> {
+        check_results_len(lookups, num_channels, results)?;
+        if lookups.is_empty() || num_channels == 0 {
+            return Ok(());
+        }
+        let num_threads = if num_threads == 0 {
+            std::thread::available_parallelism().map_or(1, |count| count.get())
+        } else {
+            num_threads
+        };
+        let chunk_len = lookups.len().div_ceil(num_threads);
+        let result_chunk_len = chunk_len * num_channels as usize;
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = lookups
+                .chunks(chunk_len)
+                .zip(results.chunks_mut(result_chunk_len))
+                .map(|(lookups, results)| {
+                    scope.spawn(move || {
+                        let mut filter = Filter::new(texture, options)?;
+                        filter.eval_batch(lookups, first_channel, num_channels, results)
+                    })
+                })
+                .collect();
+
+            workers.into_iter().try_for_each(|worker| {
+                worker.join().unwrap_or_else(|_| {
+                    Err(Error::Message(
+                        "ptex: Filter::eval_parallel() worker panicked".to_string(),
+                    ))
+                })
+            })
+        })
+    }
+}
+
+/// Ensure that a results buffer can hold num_channels values for every lookup.
+fn check_results_len(
+    lookups: &[FilterLookup],
+    num_channels: i32,
+    results: &[f32],
+) -> Result<(), Error> {
+    if num_channels < 0 {
+        return Err(Error::Message(format!(
+            "ptex: invalid number of channels {num_channels}"
+        )));
+    }
+    let required = lookups.len() * num_channels as usize;
+    if results.len() < required {
+        return Err(Error::BufferTooSmall {
+            required,
+            actual: results.len(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{InputHandler, InputStream};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Input handler that reads Ptex files from memory.
+///
+/// Files are registered by path using [`MemoryInputHandler::insert()`] and are opened
+/// when the same path is passed to [`Cache::get()`](crate::Cache::get).
+/// File data is shared between the handler and all of its open streams.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryInputHandler {
+    files: HashMap<String, Arc<[u8]>>,
+}
+
+impl MemoryInputHandler {
+    /// Create an empty MemoryInputHandler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the contents of a file. Existing contents for the path are replaced.
+    pub fn insert<P: AsRef<std::path::Path>>(&mut self, filename: P, bytes: impl Into<Arc<[u8]>>) {
+        let filename = filename.as_ref().to_string_lossy().to_string();
+        self.files.insert(filename, bytes.into());
+    }
+
+    /// Return true if contents have been registered for the path.
+    pub fn contains<P: AsRef<std::path::Path>>(&self, filename: P) -> bool {
+        self.files
+            .contains_key(filename.as_ref().to_string_lossy().as_ref())
+    }
+}
+
+impl InputHandler for MemoryInputHandler {
+    fn open(&self, path: &str) -> std::io::Result<Box<dyn InputStream>> {
+        match self.files.get(path) {
+            Some(bytes) => Ok(Box::new(std::io::Cursor::new(bytes.clone()))),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("ptex: {path:?} not found in MemoryInputHandler"),
+            )),
+        }
+    }
+}
@@ -4,6 +4,7 @@ use ptex_sys as sys;
 /// Errors returned by the ptex crate.
 mod error;
 pub use error::Error;
+pub use sys::ErrorHandler;
 
 /// Core Ptex data types.
 mod types;
@@ -13,21 +14,52 @@ pub use types::DataType;
 pub use types::EdgeFilterMode;
 pub use types::EdgeId;
 pub use types::FaceInfo;
+pub use types::FilterType;
 pub use types::MeshType;
 pub use types::MetaDataType;
 pub use types::OneValue;
 pub use types::Res;
+pub use types::Texel;
 // Half-float type.
 pub use half::f16;
 
+/// Custom input handlers for reading Ptex files from any source.
+mod input;
+pub use input::MemoryInputHandler;
+pub use sys::InputHandler;
+pub use sys::InputStream;
+
 /// Read Ptex files.
 mod reader;
 pub use reader::Cache;
+pub use reader::CacheStats;
 
 /// Write Ptex files.
 mod writer;
+pub use writer::CancellationToken;
+pub use writer::CloseProgress;
 pub use writer::Writer;
+pub use writer::WriterOptions;
 
 /// Access texture data for Ptex files.
 mod texture;
+pub use texture::TexelLocation;
 pub use texture::Texture;
+
+/// Read meta data from Ptex files.
+mod metadata;
+pub use metadata::MetaData;
+pub use metadata::MetaValue;
+
+/// Filtered texture lookups.
+mod filter;
+pub use filter::Filter;
+pub use filter::FilterLookup;
+pub use filter::FilterOptions;
+
+/// Validate the consistency of Ptex files.
+mod validate;
+pub use validate::AdjacencyReport;
+pub use validate::AdjacencyViolation;
+
+pub mod mesh;
@@ -0,0 +1,617 @@
+//! Compute Ptex face adjacency from polygon mesh topology.
+//!
+//! Meshes are described in the same way as most scene description formats: a list of
+//! face-vertex counts with one entry per face, and a flat list of face-vertex indices.
+//! Edge `i` of a face runs from its vertex `i` to vertex `i + 1`, so for a quad the
+//! edges are visited in [`EdgeId`] order: bottom, right, top and left.
+//!
+//! Meshes with arbitrary polygons can be textured with quad Ptex files by splitting
+//! every polygon that is not a quad into subfaces, see [`MeshAdjacency::from_polygons()`].
+//!
+//! Triangle faces use edge ids 0, 1 and 2, which correspond to [`EdgeId::Bottom`],
+//! [`EdgeId::Right`] and [`EdgeId::Top`]. With barycentric (u, v) coordinates, vertex 0
+//! is at (0, 0), vertex 1 at (1, 0) and vertex 2 at (0, 1), so edge 0 lies on v = 0,
+//! edge 1 on u + v = 1 and edge 2 on u = 0. The fourth adjacent face of a triangle is
+//! always -1.
+//!
+//! # Triangle texel layout
+//!
+//! A triangle face with resolution `res` (where `res.u() == res.v()`) is divided into
+//! `res * res` sub-triangles that are stored in a square grid of texels. The upright
+//! sub-triangle in grid cell (i, j) is stored at texel (i, j), which lies in the lower-left
+//! half of the grid where `i + j < res`. The inverted sub-triangle in grid cell (i, j) is
+//! stored at texel (res - 1 - j, res - 1 - i) in the upper-right half of the grid, as
+//! read by Ptex's point filter.
+//! [`barycentric_to_texel()`] and [`texel_to_barycentric()`] convert between the two.
+//!
+//! ```
+//! # fn main() -> Result<(), ptex::Error> {
+//! // Two quads that share the edge between vertices 1 and 4.
+//! let counts = [4, 4];
+//! let indices = [0, 1, 4, 3, 1, 2, 5, 4];
+//! let mesh = ptex::mesh::MeshAdjacency::from_quads(&counts, &indices)?;
+//! assert_eq!(mesh.adjacent_faces(0), [-1, 1, -1, -1]);
+//! assert_eq!(mesh.adjacent_edges(0)[1], ptex::EdgeId::Left);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::EDGE_IDS;
+use crate::writer::AsMetaData;
+use crate::{EdgeId, Error, FaceInfo, MeshType, Res, Texture, Writer};
+use std::collections::HashMap;
+use std::ffi::CString;
+
+/// Meta data key for the number of vertices in each face of the base mesh.
+pub const FACE_VERT_COUNTS_KEY: &str = "PtexFaceVertCounts";
+/// Meta data key for the vertex indices of each face of the base mesh.
+pub const FACE_VERT_INDICES_KEY: &str = "PtexFaceVertIndices";
+/// Meta data key for the xyz positions of the vertices of the base mesh.
+pub const VERT_POSITIONS_KEY: &str = "PtexVertPositions";
+
+/// Topology problems found while computing adjacency.
+///
+/// Diagnostics do not prevent adjacency from being computed. Edges with a
+/// diagnostic other than [`Diagnostic::InconsistentOrientation`] have no
+/// adjacent face.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// An edge is used by a single face.
+    BoundaryEdge {
+        /// Face that uses the edge.
+        face_id: i32,
+        /// Edge of the face.
+        edge: EdgeId,
+    },
+    /// An edge is shared by more than two faces.
+    NonManifoldEdge {
+        /// Vertices of the edge.
+        vertices: [i32; 2],
+        /// Faces that use the edge.
+        face_ids: Vec<i32>,
+    },
+    /// Two faces share an edge but traverse it in the same direction, so their
+    /// windings disagree. The faces are still connected.
+    InconsistentOrientation {
+        /// Vertices of the edge.
+        vertices: [i32; 2],
+        /// Faces that share the edge.
+        face_ids: [i32; 2],
+    },
+    /// An edge starts and ends at the same vertex.
+    DegenerateEdge {
+        /// Face that uses the edge.
+        face_id: i32,
+        /// Edge of the face.
+        edge: EdgeId,
+    },
+}
+
+/// Ptex adjacency for every face of a mesh.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshAdjacency {
+    adjacent_faces: Vec<[i32; 4]>,
+    adjacent_edges: Vec<[EdgeId; 4]>,
+    /// True for faces that are subfaces of a split polygon.
+    subfaces: Vec<bool>,
+    /// Ptex face id of the first face of each polygon, and the polygon's vertex count.
+    polygons: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl MeshAdjacency {
+    /// Compute adjacency for a quad mesh.
+    ///
+    /// Parameters:
+    /// - face_vertex_counts: Number of vertices in each face. Every face must be a quad.
+    /// - face_vertex_indices: Vertex indices for each face, concatenated.
+    ///
+    /// Face ids in the result match the order of the faces in the mesh.
+    /// [`Error::Message`] is returned when a face is not a quad, when a vertex index
+    /// is negative or when the number of indices does not match the face-vertex counts.
+    pub fn from_quads(
+        face_vertex_counts: &[i32],
+        face_vertex_indices: &[i32],
+    ) -> Result<Self, Error> {
+        check_face_vertex_counts(face_vertex_counts, |count| count == 4, "a quad")?;
+        Self::from_faces(face_vertex_counts, face_vertex_indices, false)
+    }
+
+    /// Compute adjacency for a triangle mesh, for use with [`MeshType::Triangle`].
+    ///
+    /// Every face must be a triangle. See [`MeshAdjacency::from_quads()`] for a
+    /// description of the parameters.
+    ///
+    /// [`MeshType::Triangle`]: crate::MeshType::Triangle
+    pub fn from_triangles(
+        face_vertex_counts: &[i32],
+        face_vertex_indices: &[i32],
+    ) -> Result<Self, Error> {
+        check_face_vertex_counts(face_vertex_counts, |count| count == 3, "a triangle")?;
+        Self::from_faces(face_vertex_counts, face_vertex_indices, false)
+    }
+
+    /// Compute the Ptex face layout and adjacency for a polygon mesh with quad textures.
+    ///
+    /// Following the Ptex convention, each quad is a single Ptex face and every other
+    /// polygon with n vertices is split into n subfaces. Subface i covers corner i of the
+    /// polygon: its bottom edge is the first half of polygon edge i, its left edge is the
+    /// second half of polygon edge i - 1, and its right and top edges are shared with
+    /// subfaces i + 1 and i - 1. Subfaces of a polygon have consecutive face ids, so
+    /// [`MeshAdjacency::num_faces()`] is generally larger than the number of polygons.
+    /// Use [`MeshAdjacency::face_id()`] to map polygon corners to Ptex face ids.
+    ///
+    /// When a face is adjacent to a pair of subfaces, the adjacent face is the first
+    /// subface encountered in a counter-clockwise traversal of the face's edge.
+    ///
+    /// Every polygon must have at least three vertices. See [`MeshAdjacency::from_quads()`]
+    /// for a description of the parameters.
+    pub fn from_polygons(
+        face_vertex_counts: &[i32],
+        face_vertex_indices: &[i32],
+    ) -> Result<Self, Error> {
+        check_face_vertex_counts(face_vertex_counts, |count| count >= 3, "at least 3")?;
+        Self::from_faces(face_vertex_counts, face_vertex_indices, true)
+    }
+
+    /// Compute adjacency for polygons, optionally splitting non-quads into subfaces.
+    fn from_faces(
+        face_vertex_counts: &[i32],
+        face_vertex_indices: &[i32],
+        split_non_quads: bool,
+    ) -> Result<Self, Error> {
+        let faces = split_faces(face_vertex_counts, face_vertex_indices)?;
+
+        let mut adjacency = Self::default();
+        for vertices in &faces {
+            let first_face = adjacency.subfaces.len();
+            adjacency.polygons.push((first_face, vertices.len()));
+            if split_non_quads && vertices.len() != 4 {
+                adjacency.subfaces.resize(first_face + vertices.len(), true);
+            } else {
+                adjacency.subfaces.push(false);
+            }
+        }
+        adjacency.adjacent_faces = vec![[-1; 4]; adjacency.subfaces.len()];
+        adjacency.adjacent_edges = vec![[EdgeId::Bottom; 4]; adjacency.subfaces.len()];
+        adjacency.connect(&faces);
+
+        Ok(adjacency)
+    }
+
+    /// Return the number of Ptex faces.
+    pub fn num_faces(&self) -> usize {
+        self.adjacent_faces.len()
+    }
+
+    /// Return the number of polygons in the mesh.
+    pub fn num_polygons(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Return the Ptex face id for a corner of a polygon.
+    ///
+    /// Every corner of a polygon that is not split maps to the same face id. For a split
+    /// polygon, corner i maps to subface i. Returns None if the polygon or corner is out
+    /// of range.
+    pub fn face_id(&self, polygon: usize, corner: usize) -> Option<i32> {
+        let &(first_face, num_vertices) = self.polygons.get(polygon)?;
+        if corner >= num_vertices {
+            return None;
+        }
+        if self.subfaces[first_face] {
+            Some((first_face + corner) as i32)
+        } else {
+            Some(first_face as i32)
+        }
+    }
+
+    /// Return true if a face is a subface of a split polygon.
+    ///
+    /// Panics if face_id is out of range.
+    pub fn is_subface(&self, face_id: usize) -> bool {
+        self.subfaces[face_id]
+    }
+
+    /// Return the adjacent face ids of a face, in [`EdgeId`] order. Faces without a
+    /// neighbor across an edge have an adjacent face id of -1.
+    ///
+    /// Panics if face_id is out of range.
+    pub fn adjacent_faces(&self, face_id: usize) -> [i32; 4] {
+        self.adjacent_faces[face_id]
+    }
+
+    /// Return the edge of each adjacent face that is shared with a face, in [`EdgeId`] order.
+    ///
+    /// Panics if face_id is out of range.
+    pub fn adjacent_edges(&self, face_id: usize) -> [EdgeId; 4] {
+        self.adjacent_edges[face_id]
+    }
+
+    /// Return a FaceInfo with the specified resolution, the adjacency of a face and
+    /// the subface flag.
+    ///
+    /// Panics if face_id is out of range.
+    pub fn face_info<T: Into<Res>>(&self, face_id: usize, res: T) -> FaceInfo {
+        let adjacent_edges = self.adjacent_edges[face_id].map(|edge| edge as i32);
+        FaceInfo::from_res_and_adjacency(
+            res,
+            &self.adjacent_faces[face_id],
+            &adjacent_edges,
+            self.subfaces[face_id],
+        )
+    }
+
+    /// Return the topology problems found while computing adjacency.
+    ///
+    /// Diagnostics refer to Ptex face ids. For split polygons, an edge is reported
+    /// using the subface that holds the start of the edge.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Return the Ptex faces and edges that cover a polygon edge, in the order of the
+    /// polygon's vertices.
+    fn edge_segments(&self, polygon: usize, edge: usize) -> Vec<(usize, EdgeId)> {
+        let (first_face, num_vertices) = self.polygons[polygon];
+        if self.subfaces[first_face] {
+            vec![
+                (first_face + edge, EdgeId::Bottom),
+                (first_face + (edge + 1) % num_vertices, EdgeId::Left),
+            ]
+        } else {
+            vec![(first_face, EDGE_IDS[edge])]
+        }
+    }
+
+    /// Record that an edge of a face is adjacent to an edge of another face.
+    fn set_adjacent(&mut self, (face_id, edge): (usize, EdgeId), adjacent: (usize, EdgeId)) {
+        self.adjacent_faces[face_id][edge as usize] = adjacent.0 as i32;
+        self.adjacent_edges[face_id][edge as usize] = adjacent.1;
+    }
+
+    /// Connect faces that share an edge. Polygon ids are indices into faces.
+    fn connect(&mut self, faces: &[&[i32]]) {
+        // Polygons and edges that use each undirected edge, in the order they are encountered.
+        let mut edge_uses: HashMap<[i32; 2], Vec<(usize, usize)>> = HashMap::new();
+        for (polygon, vertices) in faces.iter().enumerate() {
+            for edge in 0..vertices.len() {
+                let [v0, v1] = edge_vertices(vertices, edge);
+                if v0 != v1 {
+                    edge_uses
+                        .entry([v0.min(v1), v0.max(v1)])
+                        .or_default()
+                        .push((polygon, edge));
+                }
+            }
+        }
+
+        for (polygon, vertices) in faces.iter().enumerate() {
+            let (first_face, num_vertices) = self.polygons[polygon];
+            if self.subfaces[first_face] {
+                // Connect the subfaces around the center of the polygon.
+                for corner in 0..num_vertices {
+                    let next = first_face + (corner + 1) % num_vertices;
+                    let prev = first_face + (corner + num_vertices - 1) % num_vertices;
+                    self.set_adjacent((first_face + corner, EdgeId::Right), (next, EdgeId::Top));
+                    self.set_adjacent((first_face + corner, EdgeId::Top), (prev, EdgeId::Right));
+                }
+            }
+
+            for edge in 0..vertices.len() {
+                let segments = self.edge_segments(polygon, edge);
+                let (face_id, edge_id) = segments[0];
+                let [v0, v1] = edge_vertices(vertices, edge);
+                if v0 == v1 {
+                    self.diagnostics.push(Diagnostic::DegenerateEdge {
+                        face_id: face_id as i32,
+                        edge: edge_id,
+                    });
+                    continue;
+                }
+                let key = [v0.min(v1), v0.max(v1)];
+                let uses = &edge_uses[&key];
+                match uses.as_slice() {
+                    [_] => self.diagnostics.push(Diagnostic::BoundaryEdge {
+                        face_id: face_id as i32,
+                        edge: edge_id,
+                    }),
+                    [first, second] => {
+                        let (other_polygon, other_edge) = if *first == (polygon, edge) {
+                            *second
+                        } else {
+                            *first
+                        };
+                        let other_vertices = edge_vertices(faces[other_polygon], other_edge);
+                        let mut other_segments = self.edge_segments(other_polygon, other_edge);
+                        if other_vertices == [v0, v1] {
+                            // Report a shared edge once, when visiting its first use.
+                            if *first == (polygon, edge) {
+                                self.diagnostics.push(Diagnostic::InconsistentOrientation {
+                                    vertices: key,
+                                    face_ids: [face_id as i32, other_segments[0].0 as i32],
+                                });
+                            }
+                        } else {
+                            // Visit the other edge's segments in this edge's direction.
+                            other_segments.reverse();
+                        }
+                        for (index, &segment) in segments.iter().enumerate() {
+                            let adjacent = other_segments[index.min(other_segments.len() - 1)];
+                            self.set_adjacent(segment, adjacent);
+                        }
+                    }
+                    _ => {
+                        if uses[0] == (polygon, edge) {
+                            let face_ids = uses
+                                .iter()
+                                .map(|&(polygon, edge)| {
+                                    self.edge_segments(polygon, edge)[0].0 as i32
+                                })
+                                .collect();
+                            self.diagnostics.push(Diagnostic::NonManifoldEdge {
+                                vertices: key,
+                                face_ids,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The base mesh stored in the standard Ptex mesh meta data keys.
+///
+/// Ptex files conventionally embed their base mesh in the [`FACE_VERT_COUNTS_KEY`] and
+/// [`FACE_VERT_INDICES_KEY`] entries, stored as Int32 values, and the
+/// [`VERT_POSITIONS_KEY`] entry, stored as Float values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PtexMesh {
+    /// Number of vertices in each polygon.
+    pub face_vertex_counts: Vec<i32>,
+    /// Vertex indices for each polygon, concatenated.
+    pub face_vertex_indices: Vec<i32>,
+    /// Vertex positions, stored as consecutive x, y, z values.
+    pub vertex_positions: Vec<f32>,
+}
+
+impl PtexMesh {
+    /// Create a mesh from face-vertex counts, face-vertex indices and vertex positions.
+    pub fn new(
+        face_vertex_counts: Vec<i32>,
+        face_vertex_indices: Vec<i32>,
+        vertex_positions: Vec<f32>,
+    ) -> Self {
+        Self {
+            face_vertex_counts,
+            face_vertex_indices,
+            vertex_positions,
+        }
+    }
+
+    /// Read the mesh from a texture's meta data.
+    ///
+    /// The mesh is validated against the texture's mesh type and face count, see
+    /// [`PtexMesh::validate()`].
+    pub fn from_texture(texture: &Texture<'_>) -> Result<Self, Error> {
+        let meta_data = texture.meta_data()?;
+        let mesh = Self {
+            face_vertex_counts: meta_data.get_i32(FACE_VERT_COUNTS_KEY)?.to_vec(),
+            face_vertex_indices: meta_data.get_i32(FACE_VERT_INDICES_KEY)?.to_vec(),
+            vertex_positions: meta_data.get_f32(VERT_POSITIONS_KEY)?.to_vec(),
+        };
+        mesh.validate(texture.mesh_type(), texture.num_faces())?;
+
+        Ok(mesh)
+    }
+
+    /// Write the mesh to the meta data of a file.
+    ///
+    /// The mesh is validated against the writer's mesh type and face count before
+    /// any meta data is written.
+    pub fn write(&self, writer: &Writer) -> Result<(), Error> {
+        self.validate(writer.mesh_type(), writer.num_faces())?;
+        write_key(
+            writer,
+            FACE_VERT_COUNTS_KEY,
+            self.face_vertex_counts.as_slice(),
+        )?;
+        write_key(
+            writer,
+            FACE_VERT_INDICES_KEY,
+            self.face_vertex_indices.as_slice(),
+        )?;
+        write_key(writer, VERT_POSITIONS_KEY, self.vertex_positions.as_slice())
+    }
+
+    /// Return the number of vertices.
+    pub fn num_vertices(&self) -> usize {
+        self.vertex_positions.len() / 3
+    }
+
+    /// Return the number of Ptex faces the mesh is textured with for a mesh type.
+    ///
+    /// Triangle meshes have one face per polygon. Quad meshes have one face per quad
+    /// and one subface per vertex of every other polygon, see
+    /// [`MeshAdjacency::from_polygons()`].
+    pub fn num_ptex_faces(&self, mesh_type: MeshType) -> usize {
+        match mesh_type {
+            MeshType::Triangle => self.face_vertex_counts.len(),
+            MeshType::Quad => self
+                .face_vertex_counts
+                .iter()
+                .map(|&count| if count == 4 { 1 } else { count.max(0) as usize })
+                .sum(),
+        }
+    }
+
+    /// Ensure that the mesh is well-formed and matches a mesh type and face count.
+    ///
+    /// Triangle meshes may only contain triangles. Every vertex index must refer to a
+    /// vertex position, and [`PtexMesh::num_ptex_faces()`] must equal num_faces.
+    pub fn validate(&self, mesh_type: MeshType, num_faces: i32) -> Result<(), Error> {
+        let (is_valid, expected): (fn(i32) -> bool, _) = match mesh_type {
+            MeshType::Triangle => (|count| count == 3, "a triangle"),
+            MeshType::Quad => (|count| count >= 3, "at least 3"),
+        };
+        check_face_vertex_counts(&self.face_vertex_counts, is_valid, expected)?;
+        split_faces(&self.face_vertex_counts, &self.face_vertex_indices)?;
+        if !self.vertex_positions.len().is_multiple_of(3) {
+            return Err(Error::Message(format!(
+                "ptex: {} vertex position values is not a multiple of 3",
+                self.vertex_positions.len()
+            )));
+        }
+        let num_vertices = self.num_vertices();
+        if let Some(index) = self
+            .face_vertex_indices
+            .iter()
+            .find(|&&index| index as usize >= num_vertices)
+        {
+            return Err(Error::Message(format!(
+                "ptex: vertex index {index} is out of range for {num_vertices} vertices"
+            )));
+        }
+        let num_ptex_faces = self.num_ptex_faces(mesh_type);
+        if num_ptex_faces != num_faces.max(0) as usize {
+            return Err(Error::Message(format!(
+                "ptex: {mesh_type:?} mesh has {num_ptex_faces} faces, expected {num_faces}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Compute the Ptex adjacency of the mesh for a mesh type.
+    ///
+    /// Triangle meshes use [`MeshAdjacency::from_triangles()`] and quad meshes use
+    /// [`MeshAdjacency::from_polygons()`].
+    pub fn adjacency(&self, mesh_type: MeshType) -> Result<MeshAdjacency, Error> {
+        match mesh_type {
+            MeshType::Triangle => {
+                MeshAdjacency::from_triangles(&self.face_vertex_counts, &self.face_vertex_indices)
+            }
+            MeshType::Quad => {
+                MeshAdjacency::from_polygons(&self.face_vertex_counts, &self.face_vertex_indices)
+            }
+        }
+    }
+}
+
+/// Write a single meta data entry.
+fn write_key<T: AsMetaData>(writer: &Writer, key: &str, values: T) -> Result<(), Error> {
+    let key_cstr = CString::new(key)
+        .map_err(|err| Error::Message(format!("ptex: invalid meta data key {key:?}: {err}")))?;
+    writer.write_meta_data(&key_cstr, values)
+}
+
+/// Ensure that every face-vertex count is accepted by a predicate.
+fn check_face_vertex_counts(
+    face_vertex_counts: &[i32],
+    is_valid: impl Fn(i32) -> bool,
+    expected: &str,
+) -> Result<(), Error> {
+    for (face_id, &count) in face_vertex_counts.iter().enumerate() {
+        if !is_valid(count) {
+            return Err(Error::Message(format!(
+                "ptex: face {face_id} has {count} vertices, expected {expected}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Split face-vertex indices into one slice per face.
+fn split_faces<'a>(
+    face_vertex_counts: &[i32],
+    face_vertex_indices: &'a [i32],
+) -> Result<Vec<&'a [i32]>, Error> {
+    if let Some(count) = face_vertex_counts.iter().find(|&&count| count < 0) {
+        return Err(Error::Message(format!(
+            "ptex: invalid face-vertex count {count}"
+        )));
+    }
+    let num_indices = face_vertex_counts
+        .iter()
+        .map(|&count| count as usize)
+        .sum::<usize>();
+    if num_indices != face_vertex_indices.len() {
+        return Err(Error::Message(format!(
+            "ptex: face-vertex counts require {num_indices} indices, found {}",
+            face_vertex_indices.len()
+        )));
+    }
+    if let Some(index) = face_vertex_indices.iter().find(|&&index| index < 0) {
+        return Err(Error::Message(format!(
+            "ptex: invalid vertex index {index}"
+        )));
+    }
+
+    let mut faces = Vec::with_capacity(face_vertex_counts.len());
+    let mut start = 0;
+    for &count in face_vertex_counts {
+        let end = start + count as usize;
+        faces.push(&face_vertex_indices[start..end]);
+        start = end;
+    }
+
+    Ok(faces)
+}
+
+/// Return the start and end vertices of an edge of a face.
+fn edge_vertices(vertices: &[i32], edge: usize) -> [i32; 2] {
+    [vertices[edge], vertices[(edge + 1) % vertices.len()]]
+}
+
+/// Return the texel that holds a barycentric (u, v) coordinate of a triangle face.
+///
+/// Coordinates are clamped to the triangle. Points on the boundary between two
+/// sub-triangles map to the upright sub-triangle. See the
+/// [module documentation](self#triangle-texel-layout) for the texel layout.
+pub fn barycentric_to_texel(res: Res, u: f32, v: f32) -> (i32, i32) {
+    let size = res.u();
+    let mut u = u.clamp(0.0, 1.0);
+    let mut v = v.clamp(0.0, 1.0);
+    if u + v > 1.0 {
+        let scale = 1.0 / (u + v);
+        u *= scale;
+        v *= scale;
+    }
+    let x = u * size as f32;
+    let y = v * size as f32;
+    let mut ui = (x.floor() as i32).min(size - 1);
+    let mut vi = (y.floor() as i32).min(size - 1);
+    // Points on the long edge of the triangle can land outside of the upright half.
+    if ui + vi > size - 1 {
+        if ui > 0 {
+            ui -= 1;
+        } else {
+            vi -= 1;
+        }
+    }
+    if (x - ui as f32) + (y - vi as f32) > 1.0 {
+        (size - 1 - vi, size - 1 - ui)
+    } else {
+        (ui, vi)
+    }
+}
+
+/// Return the barycentric (u, v) coordinate at the center of a texel of a triangle face.
+///
+/// Returns None if the texel is outside of the face's resolution. See the
+/// [module documentation](self#triangle-texel-layout) for the texel layout.
+pub fn texel_to_barycentric(res: Res, u: i32, v: i32) -> Option<(f32, f32)> {
+    let size = res.u();
+    if u < 0 || v < 0 || u >= size || v >= size {
+        return None;
+    }
+    let (ui, vi, offset) = if u + v < size {
+        (u, v, 1.0 / 3.0)
+    } else {
+        (size - 1 - v, size - 1 - u, 2.0 / 3.0)
+    };
+    Some((
+        (ui as f32 + offset) / size as f32,
+        (vi as f32 + offset) / size as f32,
+    ))
+}
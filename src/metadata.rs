@@ -0,0 +1,224 @@
+use crate::{sys, Error, MetaDataType, Texture};
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+
+/// Interface for reading meta data from a Ptex file.
+///
+/// MetaData instances are acquired via [`Texture::meta_data()`](crate::Texture::meta_data)
+/// and borrow from the Texture that created them. Values returned by the typed getters
+/// are borrowed from the MetaData and remain valid until it is dropped.
+pub struct MetaData<'a> {
+    ptr: *mut sys::PtexMetaData,
+    texture: std::marker::PhantomData<&'a Texture<'a>>,
+}
+
+impl Drop for MetaData<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::ptexmetadata_release(self.ptr);
+        }
+    }
+}
+
+/// An owned meta data value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetaValue {
+    /// String value.
+    String(String),
+    /// Signed 8-bit integer values.
+    Int8(Vec<i8>),
+    /// Signed 16-bit integer values.
+    Int16(Vec<i16>),
+    /// Signed 32-bit integer values.
+    Int32(Vec<i32>),
+    /// Single-precision (32-bit) floating point values.
+    Float(Vec<f32>),
+    /// Double-precision (64-bit) floating point values.
+    Double(Vec<f64>),
+}
+
+impl MetaValue {
+    /// Return the MetaDataType for the value.
+    pub fn meta_data_type(&self) -> MetaDataType {
+        match self {
+            MetaValue::String(_) => MetaDataType::String,
+            MetaValue::Int8(_) => MetaDataType::Int8,
+            MetaValue::Int16(_) => MetaDataType::Int16,
+            MetaValue::Int32(_) => MetaDataType::Int32,
+            MetaValue::Float(_) => MetaDataType::Float,
+            MetaValue::Double(_) => MetaDataType::Double,
+        }
+    }
+}
+
+impl MetaData<'_> {
+    /// Wrap a PtexMetaData pointer returned by ptextexture_get_meta_data().
+    pub(crate) fn new(ptr: *mut sys::PtexMetaData) -> Self {
+        Self {
+            ptr,
+            texture: std::marker::PhantomData,
+        }
+    }
+
+    /// Return the number of meta data entries.
+    pub fn num_keys(&self) -> i32 {
+        unsafe { sys::ptexmetadata_num_keys(self.ptr) }
+    }
+
+    /// Return the key and type for the entry at the specified index.
+    ///
+    /// None is returned when the index is out of range or when the key is not valid UTF-8.
+    pub fn key(&self, index: i32) -> Option<(&str, MetaDataType)> {
+        if index < 0 || index >= self.num_keys() {
+            return None;
+        }
+        let mut key: *const c_char = std::ptr::null();
+        let mut typ = sys::MetaDataType::String;
+        unsafe {
+            sys::ptexmetadata_get_key(self.ptr, index, &mut key, &mut typ);
+        }
+        if key.is_null() {
+            return None;
+        }
+        let key = unsafe { CStr::from_ptr(key) }.to_str().ok()?;
+        Some((key, MetaDataType::from(typ)))
+    }
+
+    /// Iterate over the key and type for every meta data entry.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, MetaDataType)> + '_ {
+        (0..self.num_keys()).filter_map(|index| self.key(index))
+    }
+
+    /// Return the MetaDataType for a key, or None if the key is not present.
+    pub fn meta_data_type(&self, key: &str) -> Option<MetaDataType> {
+        self.find_key(key).ok().map(|(_, typ)| typ)
+    }
+
+    /// Return the string value for a key.
+    pub fn get_str(&self, key: &str) -> Result<&str, Error> {
+        let (index, typ) = self.find_key(key)?;
+        check_meta_data_type(key, MetaDataType::String, typ)?;
+        let mut value: *mut u8 = std::ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            sys::ptexmetadata_get_value_at_index(
+                self.ptr,
+                index,
+                typ.into(),
+                &mut value,
+                &mut count,
+            );
+        }
+        if value.is_null() {
+            return Ok("");
+        }
+        unsafe { CStr::from_ptr(value as *const c_char) }
+            .to_str()
+            .map_err(|err| Error::Message(format!("ptex: meta data {key:?}: {err}")))
+    }
+
+    /// Return the signed 8-bit integer values for a key.
+    pub fn get_i8(&self, key: &str) -> Result<&[i8], Error> {
+        self.get_values(key, MetaDataType::Int8)
+    }
+
+    /// Return the signed 16-bit integer values for a key.
+    pub fn get_i16(&self, key: &str) -> Result<&[i16], Error> {
+        self.get_values(key, MetaDataType::Int16)
+    }
+
+    /// Return the signed 32-bit integer values for a key.
+    pub fn get_i32(&self, key: &str) -> Result<&[i32], Error> {
+        self.get_values(key, MetaDataType::Int32)
+    }
+
+    /// Return the single-precision floating point values for a key.
+    pub fn get_f32(&self, key: &str) -> Result<&[f32], Error> {
+        self.get_values(key, MetaDataType::Float)
+    }
+
+    /// Return the double-precision floating point values for a key.
+    pub fn get_f64(&self, key: &str) -> Result<&[f64], Error> {
+        self.get_values(key, MetaDataType::Double)
+    }
+
+    /// Return an owned copy of the value for a key.
+    pub fn get(&self, key: &str) -> Result<MetaValue, Error> {
+        let (_, typ) = self.find_key(key)?;
+        let value = match typ {
+            MetaDataType::String => MetaValue::String(self.get_str(key)?.to_string()),
+            MetaDataType::Int8 => MetaValue::Int8(self.get_i8(key)?.to_vec()),
+            MetaDataType::Int16 => MetaValue::Int16(self.get_i16(key)?.to_vec()),
+            MetaDataType::Int32 => MetaValue::Int32(self.get_i32(key)?.to_vec()),
+            MetaDataType::Float => MetaValue::Float(self.get_f32(key)?.to_vec()),
+            MetaDataType::Double => MetaValue::Double(self.get_f64(key)?.to_vec()),
+        };
+        Ok(value)
+    }
+
+    /// Collect owned copies of every meta data entry into a map.
+    pub fn to_map(&self) -> Result<HashMap<String, MetaValue>, Error> {
+        self.keys()
+            .map(|(key, _)| Ok((key.to_string(), self.get(key)?)))
+            .collect()
+    }
+
+    /// Find the index and type for a key.
+    fn find_key(&self, key: &str) -> Result<(i32, MetaDataType), Error> {
+        let key_cstr = CString::new(key)
+            .map_err(|err| Error::Message(format!("ptex: invalid meta data key {key:?}: {err}")))?;
+        let mut index = 0;
+        let mut typ = sys::MetaDataType::String;
+        let found = unsafe {
+            sys::ptexmetadata_find_key(self.ptr, key_cstr.as_ptr(), &mut index, &mut typ)
+        };
+        if !found {
+            return Err(Error::Message(format!(
+                "ptex: meta data key {key:?} not found"
+            )));
+        }
+        Ok((index, MetaDataType::from(typ)))
+    }
+
+    /// Return the array of values for a key after checking its type.
+    fn get_values<T>(&self, key: &str, expected: MetaDataType) -> Result<&[T], Error> {
+        let (index, typ) = self.find_key(key)?;
+        check_meta_data_type(key, expected, typ)?;
+        let mut value: *mut u8 = std::ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            sys::ptexmetadata_get_value_at_index(
+                self.ptr,
+                index,
+                typ.into(),
+                &mut value,
+                &mut count,
+            );
+        }
+        if value.is_null() || count <= 0 {
+            return Ok(&[]);
+        }
+        if value.align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(Error::Message(format!(
+                "ptex: meta data {key:?} is not aligned for {expected:?} values"
+            )));
+        }
+        Ok(unsafe { std::slice::from_raw_parts(value as *const T, count as usize) })
+    }
+}
+
+/// Return an error when the stored meta data type does not match the requested type.
+fn check_meta_data_type(
+    key: &str,
+    expected: MetaDataType,
+    actual: MetaDataType,
+) -> Result<(), Error> {
+    if expected != actual {
+        return Err(Error::MetaDataTypeMismatch {
+            key: key.to_string(),
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
-use crate::{sys, Error, Texture};
+use crate::error::{collect_errors, is_not_found, open_error, ErrorCollector};
+use crate::{sys, Error, ErrorHandler, InputHandler, MemoryInputHandler, Texture};
 use cxx::let_cxx_string;
+use std::sync::Arc;
 
 /// File-handle and memory cache for reading ptex files
 ///
@@ -14,13 +16,70 @@ use cxx::let_cxx_string;
 /// all threads that have access to the cache, and the data are protected
 /// with internal locks.  See ptex/PtexCache.cpp for details about the caching
 /// and locking implementation.
-pub struct Cache(*mut sys::PtexCache);
+pub struct Cache {
+    ptr: *mut sys::PtexCache,
+    /// Custom input handler used by the cache, or null when reading from the filesystem.
+    input_handler: *mut sys::PtexInputHandler,
+    /// The custom input handler, used to tell missing files from invalid files.
+    input: Option<Arc<dyn InputHandler>>,
+    /// Custom error handler used by the cache, or null when errors are printed to stderr.
+    error_handler: *mut sys::PtexErrorHandler,
+    /// Errors collected by the error handler.
+    errors: Option<Arc<ErrorCollector>>,
+}
+
+/// The Ptex cache is internally synchronized and may be shared between threads.
+/// Input and error handlers are required to be `Send + Sync`.
+unsafe impl Send for Cache {}
+unsafe impl Sync for Cache {}
+
+/// Cache statistics returned by [`Cache::stats()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    /// Memory currently used by the cache, in bytes.
+    pub mem_used: u64,
+    /// Peak memory used by the cache, in bytes.
+    pub peak_mem_used: u64,
+    /// Number of files currently open.
+    pub files_open: u64,
+    /// Peak number of files open.
+    pub peak_files_open: u64,
+    /// Number of files accessed.
+    pub files_accessed: u64,
+    /// Number of times a previously-closed file was reopened.
+    /// A high value indicates that max_files is too low for the working set.
+    pub file_reopens: u64,
+    /// Number of data blocks read from disk.
+    /// A high value indicates that max_mem is too low for the working set.
+    pub block_reads: u64,
+}
+
+impl From<sys::CacheStats> for CacheStats {
+    fn from(stats: sys::CacheStats) -> Self {
+        Self {
+            mem_used: stats.mem_used,
+            peak_mem_used: stats.peak_mem_used,
+            files_open: stats.files_open,
+            peak_files_open: stats.peak_files_open,
+            files_accessed: stats.files_accessed,
+            file_reopens: stats.file_reopens,
+            block_reads: stats.block_reads,
+        }
+    }
+}
 
 /// Drop implementation for Cache.
 impl Drop for Cache {
     fn drop(&mut self) {
         unsafe {
-            sys::ptexcache_release(self.0);
+            sys::ptexcache_release(self.ptr);
+            // The handlers must outlive the cache.
+            if !self.input_handler.is_null() {
+                sys::ptexinputhandler_release(self.input_handler);
+            }
+            if !self.error_handler.is_null() {
+                sys::ptexerrorhandler_release(self.error_handler);
+            }
         }
     }
 }
@@ -39,43 +98,208 @@ impl Cache {
     ///   rendering purposes, this should generally be set to true.
     ///   See PtexTexture and PtexWriter for more details.
     pub fn new(max_files: i32, max_mem: usize, premultiply: bool) -> Self {
-        unsafe { Self(sys::ptexcache_create(max_files, max_mem, premultiply)) }
+        Self {
+            ptr: unsafe { sys::ptexcache_create(max_files, max_mem, premultiply) },
+            input_handler: std::ptr::null_mut(),
+            input: None,
+            error_handler: std::ptr::null_mut(),
+            errors: None,
+        }
+    }
+
+    /// Create a cache that reads files using a custom [`InputHandler`].
+    ///
+    /// Filenames passed to [`Cache::get()`] are forwarded to the input handler's
+    /// `open()` method. Ptex errors are collected as described in
+    /// [`Cache::with_handlers()`]. See [`Cache::new()`] for a description of the
+    /// other parameters.
+    pub fn with_input_handler<H: InputHandler + 'static>(
+        max_files: i32,
+        max_mem: usize,
+        premultiply: bool,
+        input_handler: H,
+    ) -> Self {
+        Self::with_handlers(
+            max_files,
+            max_mem,
+            premultiply,
+            Some(Box::new(input_handler)),
+            None,
+        )
+    }
+
+    /// Create a cache that reports Ptex errors to a custom [`ErrorHandler`].
+    ///
+    /// Ptex errors are also collected as described in [`Cache::with_handlers()`].
+    /// See [`Cache::new()`] for a description of the other parameters.
+    pub fn with_error_handler<H: ErrorHandler + 'static>(
+        max_files: i32,
+        max_mem: usize,
+        premultiply: bool,
+        error_handler: H,
+    ) -> Self {
+        Self::with_handlers(
+            max_files,
+            max_mem,
+            premultiply,
+            None,
+            Some(Box::new(error_handler)),
+        )
+    }
+
+    /// Create a cache that collects Ptex errors instead of printing them to stderr.
+    ///
+    /// See [`Cache::with_handlers()`] for details.
+    pub fn with_collected_errors(max_files: i32, max_mem: usize, premultiply: bool) -> Self {
+        Self::with_handlers(max_files, max_mem, premultiply, None, None)
+    }
+
+    /// Create a cache with optional custom input and error handlers.
+    ///
+    /// Errors reported by Ptex are not printed to stderr. They are collected for the
+    /// thread that triggered them and returned in the [`Error`] from [`Cache::get()`]
+    /// and from Texture face and meta data reads. When an error handler is provided
+    /// every message is also forwarded to it. Messages reported by other operations,
+    /// such as filter evaluation, are only forwarded to the error handler.
+    ///
+    /// When no input handler is provided files are read from the filesystem.
+    /// See [`Cache::new()`] for a description of the other parameters.
+    pub fn with_handlers(
+        max_files: i32,
+        max_mem: usize,
+        premultiply: bool,
+        input_handler: Option<Box<dyn InputHandler>>,
+        error_handler: Option<Box<dyn ErrorHandler>>,
+    ) -> Self {
+        let input: Option<Arc<dyn InputHandler>> = input_handler.map(Arc::from);
+        let input_handler = match &input {
+            Some(input) => {
+                sys::ptexinputhandler_create(sys::InputHandlerBox::new(Box::new(input.clone())))
+            }
+            None => std::ptr::null_mut(),
+        };
+        let errors = Arc::new(ErrorCollector::new(error_handler));
+        let error_handler =
+            sys::ptexerrorhandler_create(sys::ErrorHandlerBox::new(Box::new(errors.clone())));
+        let ptr = unsafe {
+            sys::ptexcache_create_with_handlers(
+                max_files,
+                max_mem,
+                premultiply,
+                input_handler,
+                error_handler,
+            )
+        };
+        Self {
+            ptr,
+            input_handler,
+            input,
+            error_handler,
+            errors: Some(errors),
+        }
+    }
+
+    /// Create a cache that reads a single texture from memory.
+    ///
+    /// The texture is read by calling [`Cache::get()`] with the specified filename.
+    pub fn from_bytes<P: AsRef<std::path::Path>>(
+        filename: P,
+        bytes: impl Into<Arc<[u8]>>,
+        premultiply: bool,
+    ) -> Self {
+        let mut input_handler = MemoryInputHandler::new();
+        input_handler.insert(filename, bytes);
+        Self::with_input_handler(0, 0, premultiply, input_handler)
     }
 
     /// Return a cached Ptex Reader for the specified filename.
     /// The filename be either an absolute path, a relative path, or a path
     /// relative to the Ptex search path.
-    pub fn get<P: AsRef<std::path::Path>>(&mut self, filename: P) -> Result<Texture, Error> {
+    ///
+    /// The returned Texture borrows from the cache and cannot outlive it.
+    /// Textures may be requested from multiple threads through a shared reference.
+    pub fn get<P: AsRef<std::path::Path>>(&self, filename: P) -> Result<Texture<'_>, Error> {
         let_cxx_string!(error_str = "");
         let filename_str = filename.as_ref().to_string_lossy().to_string();
-        let texture = unsafe {
+        let (texture, messages) = collect_errors(self.errors.as_deref(), || unsafe {
             sys::ptexcache_get(
-                self.0,
+                self.ptr,
                 filename_str.as_str(),
                 error_str.as_mut().get_unchecked_mut(),
             )
-        };
-
+        });
         if texture.is_null() {
-            let default_error_message = format!("ptex: Cache::get({:?}) failed", filename.as_ref());
-            if error_str.is_empty() {
-                return Err(Error::Message(error_str.to_string()));
-            }
-            return Err(Error::Message(default_error_message));
+            return Err(open_error(
+                "Cache::get",
+                filename.as_ref(),
+                self.is_missing(filename.as_ref()),
+                &error_str.to_string_lossy(),
+                &messages,
+            ));
         }
 
-        Ok(Texture(texture))
+        Ok(Texture {
+            ptr: texture,
+            errors: self.errors.clone(),
+            cache: std::marker::PhantomData,
+        })
     }
 
     /// Set the texture search path for a PtexCache.
     pub fn set_search_path(&mut self, path: &str) {
         unsafe {
-            sys::ptexcache_set_search_path(self.0, path);
+            sys::ptexcache_set_search_path(self.ptr, path);
         }
     }
 
     /// Get the texture search path for a PtexCache.
     pub fn search_path(&self) -> String {
-        unsafe { sys::ptexcache_get_search_path(self.0) }
+        unsafe { sys::ptexcache_get_search_path(self.ptr) }
+    }
+
+    /// Return true if a file that failed to open does not exist.
+    ///
+    /// Like Ptex, relative paths are looked up in the search path when it is set.
+    /// Files are looked up with the input handler when one is installed, and on the
+    /// filesystem otherwise.
+    fn is_missing(&self, filename: &std::path::Path) -> bool {
+        let is_missing = |path: &std::path::Path| match &self.input {
+            Some(input) => is_not_found(input.open(&path.to_string_lossy())),
+            None => is_not_found(std::fs::metadata(path)),
+        };
+        let search_path = self.search_path();
+        if filename.is_relative() && !search_path.is_empty() {
+            return std::env::split_paths(&search_path).all(|dir| is_missing(&dir.join(filename)));
+        }
+        is_missing(filename)
+    }
+
+    /// Remove a texture file from the cache.
+    ///
+    /// The filename is resolved in the same way as [`Cache::get()`]. Textures that are
+    /// still in use remain valid and are released when they are dropped.
+    pub fn purge<P: AsRef<std::path::Path>>(&self, filename: P) {
+        let filename_str = filename.as_ref().to_string_lossy().to_string();
+        unsafe {
+            sys::ptexcache_purge(self.ptr, filename_str.as_str());
+        }
+    }
+
+    /// Remove all texture files from the cache.
+    ///
+    /// Textures that are still in use remain valid and are released when they are dropped.
+    pub fn purge_all(&self) {
+        unsafe {
+            sys::ptexcache_purge_all(self.ptr);
+        }
+    }
+
+    /// Return memory and file handle statistics for the cache.
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = sys::CacheStats::default();
+        unsafe {
+            sys::ptexcache_get_stats(self.ptr, &mut stats);
+        }
+        stats.into()
     }
 }
@@ -1,96 +1,214 @@
+use crate::error::{check_messages, collect_errors, is_not_found, open_error, ErrorCollector};
 use crate::sys;
-use crate::{BorderMode, DataType, EdgeFilterMode, FaceInfo, MeshType};
+use crate::types::EDGE_IDS;
+use crate::{
+    BorderMode, Cache, DataSize, DataType, EdgeFilterMode, EdgeId, Error, FaceInfo, MeshType,
+    MetaData, Res, Texel,
+};
+use cxx::let_cxx_string;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Maximum number of edges crossed by [`Texture::resolve_texel()`].
+const MAX_EDGE_CROSSINGS: usize = 16;
 
 /// Interface for reading data from a ptex file
 ///
 /// PtexTexture instances can be acquired via any of the following methods
 ///
 /// * from this crate with [`Cache::get()`](crate::Cache::get`).
+/// * from this crate with [`Texture::open()`], without a cache.
 /// * from [ptex_sys] using the [ptex_writer()](ptex_sys::ffi::ptexwriter_open) function\
 ///   or [PtexCache](ptex_sys::ffi::PtexCache) interface.
 ///
 /// Data access through this interface is returned in v-major order with all data channels
 /// interleaved per texel.
-pub struct Texture(pub(crate) *mut sys::PtexTexture);
+///
+/// # Lifetimes
+///
+/// Textures acquired from a [`Cache`](crate::Cache) borrow from the cache and cannot
+/// outlive it. The cache cannot be dropped while its textures are in use:
+///
+/// ```compile_fail
+/// let cache = ptex::Cache::new(0, 0, false);
+/// let texture = cache.get("tests/fixtures/test.ptx").unwrap();
+/// drop(cache);
+/// texture.num_faces();
+/// ```
+///
+/// Textures cannot escape the scope of their cache:
+///
+/// ```compile_fail
+/// let texture = {
+///     let cache = ptex::Cache::new(0, 0, false);
+///     cache.get("tests/fixtures/test.ptx").unwrap()
+/// };
+/// texture.num_faces();
+/// ```
+///
+/// # Thread safety
+///
+/// Textures are `Send` and `Sync`. Ptex readers protect their internal state with locks,
+/// so data may be read from a shared Texture by multiple threads at the same time.
+/// [`Filter`](crate::Filter) instances are per-thread and should be created for each thread.
+pub struct Texture<'cache> {
+    pub(crate) ptr: *mut sys::PtexTexture,
+    /// Errors reported by Ptex while reading, when the Cache collects errors.
+    pub(crate) errors: Option<Arc<ErrorCollector>>,
+    pub(crate) cache: PhantomData<&'cache Cache>,
+}
+
+/// Ptex readers are safe to use from multiple threads.
+unsafe impl Send for Texture<'_> {}
+unsafe impl Sync for Texture<'_> {}
 
-impl Drop for Texture {
+impl Drop for Texture<'_> {
     fn drop(&mut self) {
         unsafe {
-            sys::ptextexture_release(self.0);
+            sys::ptextexture_release(self.ptr);
+        }
+    }
+}
+
+impl Texture<'static> {
+    /// Open a ptex file for reading without a [`Cache`].
+    ///
+    /// The file is opened directly and is not shared with any cache. This is useful
+    /// for one-off reads. Use a Cache when reading many files or reading the same
+    /// file repeatedly.
+    ///
+    /// Parameters:
+    /// - filename: File path.
+    /// - premultiply: If true, the texture will be premultiplied by the alpha channel
+    ///   (if any) when read from disk. See [`Cache::new()`] for more details.
+    pub fn open<P: AsRef<std::path::Path>>(filename: P, premultiply: bool) -> Result<Self, Error> {
+        let_cxx_string!(error_str = "");
+        let filename_str = filename.as_ref().to_string_lossy().to_string();
+        let texture = unsafe {
+            sys::ptextexture_open(
+                filename_str.as_str(),
+                error_str.as_mut().get_unchecked_mut(),
+                premultiply,
+            )
+        };
+        if texture.is_null() {
+            return Err(open_error(
+                "Texture::open",
+                filename.as_ref(),
+                is_not_found(std::fs::metadata(filename.as_ref())),
+                &error_str.to_string_lossy(),
+                &[],
+            ));
         }
+
+        Ok(Self {
+            ptr: texture,
+            errors: None,
+            cache: PhantomData,
+        })
     }
 }
 
-impl Texture {
+impl Texture<'_> {
     pub fn is_null(&self) -> bool {
-        self.0.is_null()
+        self.ptr.is_null()
     }
 
     /// Does the texture contain any in-memory edits?
     pub fn has_edits(&self) -> bool {
-        unsafe { sys::ptextexture_has_edits(self.0) }
+        unsafe { sys::ptextexture_has_edits(self.ptr) }
     }
 
     /// Does the texture have mip-maps?
     pub fn has_mip_maps(&self) -> bool {
-        unsafe { sys::ptextexture_has_mipmaps(self.0) }
+        unsafe { sys::ptextexture_has_mipmaps(self.ptr) }
     }
 
     /// Return the alpha channels for the Texture.
     pub fn alpha_channel(&self) -> i32 {
-        unsafe { sys::ptextexture_get_alpha_channel(self.0) }
+        unsafe { sys::ptextexture_get_alpha_channel(self.ptr) }
     }
 
     /// Return the number of channels in the Texture.
     pub fn num_channels(&self) -> i32 {
-        unsafe { sys::ptextexture_get_num_channels(self.0) }
+        unsafe { sys::ptextexture_get_num_channels(self.ptr) }
     }
 
     /// Return the number of faces in the Texture.
     pub fn num_faces(&self) -> i32 {
-        unsafe { sys::ptextexture_get_num_faces(self.0) }
+        unsafe { sys::ptextexture_get_num_faces(self.ptr) }
     }
 
     /// Return a PathBuf containing the Texture's filename.
     pub fn filename(&self) -> std::path::PathBuf {
-        let path_string = unsafe { sys::ptextexture_get_path(self.0) };
+        let path_string = unsafe { sys::ptextexture_get_path(self.ptr) };
         std::path::PathBuf::from(&path_string)
     }
 
     /// Return the ptex::MeshType for the Texture.
     pub fn mesh_type(&self) -> MeshType {
-        MeshType::from(unsafe { sys::ptextexture_get_meshtype(self.0) })
+        MeshType::from(unsafe { sys::ptextexture_get_meshtype(self.ptr) })
+    }
+
+    /// Access meta data stored in the Texture.
+    pub fn meta_data(&self) -> Result<MetaData<'_>, Error> {
+        let (meta_data, messages) =
+            self.collect_errors(|| unsafe { sys::ptextexture_get_meta_data(self.ptr) });
+        let context = format!(
+            "ptex: Texture::meta_data() failed for {:?}",
+            self.filename()
+        );
+        if meta_data.is_null() {
+            check_messages(&context, messages)?;
+            return Err(Error::Message(context));
+        }
+        if let Err(err) = check_messages(&context, messages) {
+            unsafe { sys::ptexmetadata_release(meta_data) };
+            return Err(err);
+        }
+        Ok(MetaData::new(meta_data))
     }
 
     /// Return the ptex::DataType for the Texture.
     pub fn data_type(&self) -> DataType {
-        DataType::from(unsafe { sys::ptextexture_get_datatype(self.0) })
+        DataType::from(unsafe { sys::ptextexture_get_datatype(self.ptr) })
     }
 
     /// Return the border mode in the U direction.
     pub fn border_mode_u(&self) -> BorderMode {
-        BorderMode::from(unsafe { sys::ptextexture_get_border_mode_u(self.0) })
+        BorderMode::from(unsafe { sys::ptextexture_get_border_mode_u(self.ptr) })
     }
 
     /// Return the border mode in the U direction.
     pub fn border_mode_v(&self) -> BorderMode {
-        BorderMode::from(unsafe { sys::ptextexture_get_border_mode_v(self.0) })
+        BorderMode::from(unsafe { sys::ptextexture_get_border_mode_v(self.ptr) })
     }
 
     /// Return the edge filter mode.
     pub fn edge_filter_mode(&self) -> EdgeFilterMode {
-        EdgeFilterMode::from(unsafe { sys::ptextexture_get_edge_filter_mode(self.0) })
+        EdgeFilterMode::from(unsafe { sys::ptextexture_get_edge_filter_mode(self.ptr) })
     }
 
     /// Access resolution and adjacency information about a face.
-    pub fn face_info(&self, face_id: i32) -> FaceInfo {
-        FaceInfo(*unsafe { sys::ptextexture_get_face_info(self.0, face_id) })
+    ///
+    /// Returns [`Error::FaceIdOutOfRange`] when face_id is not in [0..num_faces-1].
+    pub fn face_info(&self, face_id: i32) -> Result<FaceInfo, Error> {
+        self.check_face_id(face_id)?;
+        Ok(unsafe { self.face_info_unchecked(face_id) })
+    }
+
+    /// Access resolution and adjacency information about a face without bounds checking.
+    ///
+    /// # Safety
+    /// The face_id must be in the range [0..num_faces-1].
+    pub unsafe fn face_info_unchecked(&self, face_id: i32) -> FaceInfo {
+        FaceInfo(*sys::ptextexture_get_face_info(self.ptr, face_id))
     }
 
     /// Access a single texel from the highest resolution texture .
     /// The texel data is converted to floating point (integer types
     /// are normalized 0.0 to 1.0).  A subset of the available
-    /// channels may be accessed.
+    /// channels may be accessed. The value of the first channel is returned.
     ///
     /// # Parameters
     ///
@@ -99,6 +217,8 @@ impl Texture {
     /// - `v`: V coordinate [0..vres-1]
     /// - `first_channel`: First channel to access [0..num_channels-1]
     /// - `num_channels`: Number of channels to access.
+    ///
+    /// Returns an error when the face, texel or channel range is out of bounds.
     pub fn pixel_f32(
         &self,
         face_id: i32,
@@ -106,7 +226,395 @@ impl Texture {
         v: i32,
         first_channel: i32,
         num_channels: i32,
+    ) -> Result<f32, Error> {
+        let res = self.face_info(face_id)?.resolution();
+        if u < 0 || v < 0 || u >= res.u() || v >= res.v() {
+            return Err(Error::TexelOutOfRange {
+                face_id,
+                u,
+                v,
+                ures: res.u(),
+                vres: res.v(),
+            });
+        }
+        self.check_channels(first_channel, num_channels)?;
+        Ok(unsafe { self.pixel_f32_unchecked(face_id, u, v, first_channel, num_channels) })
+    }
+
+    /// Access a single texel from the highest resolution texture without bounds checking.
+    ///
+    /// See [`Texture::pixel_f32()`] for a description of the parameters.
+    ///
+    /// # Safety
+    /// The face_id must be in the range [0..num_faces-1], u and v must be within the
+    /// face's resolution and the channel range must be within [0..num_channels-1].
+    pub unsafe fn pixel_f32_unchecked(
+        &self,
+        face_id: i32,
+        u: i32,
+        v: i32,
+        first_channel: i32,
+        num_channels: i32,
     ) -> f32 {
-        unsafe { sys::ptextexture_get_pixel(self.0, face_id, u, v, first_channel, num_channels) }
+        sys::ptextexture_get_pixel(self.ptr, face_id, u, v, first_channel, num_channels)
+    }
+
+    /// Access a single texel that may lie outside of a face.
+    ///
+    /// Texels past the edges of the face are read from the adjacent faces, as located by
+    /// [`Texture::resolve_texel()`]. Texels past a mesh boundary follow the texture's
+    /// [`BorderMode`], and are 0.0 when the border mode is [`BorderMode::Black`].
+    ///
+    /// See [`Texture::pixel_f32()`] for a description of the parameters. The u and v
+    /// coordinates may be outside of the face's resolution.
+    pub fn pixel_f32_across_edges(
+        &self,
+        face_id: i32,
+        u: i32,
+        v: i32,
+        first_channel: i32,
+        num_channels: i32,
+    ) -> Result<f32, Error> {
+        self.check_channels(first_channel, num_channels)?;
+        match self.resolve_texel(face_id, u, v)? {
+            Some(texel) => Ok(unsafe {
+                self.pixel_f32_unchecked(
+                    texel.face_id,
+                    texel.u,
+                    texel.v,
+                    first_channel,
+                    num_channels,
+                )
+            }),
+            None => Ok(0.0),
+        }
+    }
+
+    /// Locate the texel addressed by coordinates that may lie outside of a face.
+    ///
+    /// Coordinates inside the face's resolution are returned unchanged. Coordinates past
+    /// an edge are carried across to the adjacent face reported by
+    /// [`FaceInfo::adjacent_face()`] and rotated into its coordinate frame using
+    /// [`FaceInfo::adjacent_edge()`]. Every face spans the same parametric extent, so the
+    /// position along the edge and the distance past it are scaled when the faces have
+    /// different resolutions. Regular faces that border a pair of subfaces are split
+    /// between the two subfaces, and subfaces cover half of a regular face's edge.
+    ///
+    /// Coordinates past an edge with no adjacent face follow the texture's [`BorderMode`]
+    /// in that direction: [`BorderMode::Clamp`] clamps them to the face,
+    /// [`BorderMode::Periodic`] wraps them to the other side of the face and
+    /// [`BorderMode::Black`] returns `None`.
+    ///
+    /// Coordinates that are past both a u and a v edge are carried across the u edge
+    /// first. Only quad meshes are supported.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), ptex::Error> {
+    /// let cache = ptex::Cache::new(0, 0, false);
+    /// let texture = cache.get("asset.ptx")?;
+    /// // The texel just to the right of texel (ures-1, 0) in face 0.
+    /// let ures = texture.face_info(0)?.resolution().u();
+    /// if let Some(texel) = texture.resolve_texel(0, ures, 0)? {
+    ///     println!("face {} texel ({}, {})", texel.face_id, texel.u, texel.v);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve_texel(
+        &self,
+        face_id: i32,
+        u: i32,
+        v: i32,
+    ) -> Result<Option<TexelLocation>, Error> {
+        if self.mesh_type() != MeshType::Quad {
+            return Err(Error::Message(format!(
+                "ptex: cross-face texel access is not supported for triangle meshes ({:?})",
+                self.filename()
+            )));
+        }
+        let mut texel = TexelLocation { face_id, u, v };
+        let mut face_info = self.face_info(face_id)?;
+        for _ in 0..MAX_EDGE_CROSSINGS {
+            let res = face_info.resolution();
+            let (ures, vres) = (res.u(), res.v());
+            let edge = if texel.u < 0 {
+                EdgeId::Left
+            } else if texel.u >= ures {
+                EdgeId::Right
+            } else if texel.v < 0 {
+                EdgeId::Bottom
+            } else if texel.v >= vres {
+                EdgeId::Top
+            } else {
+                return Ok(Some(texel));
+            };
+
+            let adjacent_face = face_info.adjacent_face(edge as i32);
+            if adjacent_face == -1 {
+                let border_mode = match edge {
+                    EdgeId::Left | EdgeId::Right => self.border_mode_u(),
+                    EdgeId::Bottom | EdgeId::Top => self.border_mode_v(),
+                };
+                let (coord, size) = match edge {
+                    EdgeId::Left | EdgeId::Right => (&mut texel.u, ures),
+                    EdgeId::Bottom | EdgeId::Top => (&mut texel.v, vres),
+                };
+                match border_mode {
+                    BorderMode::Clamp => *coord = (*coord).clamp(0, size - 1),
+                    BorderMode::Periodic => *coord = (*coord).rem_euclid(size),
+                    BorderMode::Black => return Ok(None),
+                }
+                continue;
+            }
+
+            // Position along the edge, in the edge's direction, and distance past the
+            // edge, as fractions of the face's extent.
+            let (along, past, along_size, past_size) = match edge {
+                EdgeId::Bottom => (texel.u, -texel.v - 1, ures, vres),
+                EdgeId::Right => (texel.v, texel.u - ures, vres, ures),
+                EdgeId::Top => (ures - 1 - texel.u, texel.v - vres, ures, vres),
+                EdgeId::Left => (vres - 1 - texel.v, -texel.u - 1, vres, ures),
+            };
+            let along = (f64::from(along) + 0.5) / f64::from(along_size);
+            let mut past = (f64::from(past) + 0.5) / f64::from(past_size);
+
+            // Adjacent edges run in the opposite direction.
+            let mut adjacent_face = adjacent_face;
+            let mut adjacent_edge = face_info.adjacent_edge(edge as i32);
+            let mut adjacent_info = self.face_info(adjacent_face)?;
+            let adjacent_along = if !face_info.is_subface() && adjacent_info.is_subface() {
+                // The adjacent subface covers the first half of the edge and the other
+                // subface of its pair covers the second half.
+                past *= 2.0;
+                if along < 0.5 {
+                    1.0 - 2.0 * along
+                } else {
+                    let pair_edge = (adjacent_edge as i32 + 3) % 4;
+                    adjacent_face = adjacent_info.adjacent_face(pair_edge);
+                    adjacent_edge =
+                        EDGE_IDS[(adjacent_info.adjacent_edge(pair_edge) as usize + 3) % 4];
+                    adjacent_info = self.face_info(adjacent_face)?;
+                    2.0 - 2.0 * along
+                }
+            } else if face_info.is_subface() && !adjacent_info.is_subface() {
+                // The subface that the regular face points to covers the first half of
+                // the regular face's edge.
+                past *= 0.5;
+                let is_primary = adjacent_info.adjacent_face(adjacent_edge as i32) == texel.face_id;
+                let offset = if is_primary { 0.0 } else { 0.5 };
+                offset + (1.0 - along) * 0.5
+            } else {
+                1.0 - along
+            };
+
+            let res = adjacent_info.resolution();
+            let (ures, vres) = (res.u(), res.v());
+            let (along_size, past_size) = match adjacent_edge {
+                EdgeId::Bottom | EdgeId::Top => (ures, vres),
+                EdgeId::Right | EdgeId::Left => (vres, ures),
+            };
+            let along = (adjacent_along * f64::from(along_size)).floor() as i32;
+            let past = (past * f64::from(past_size)).floor() as i32;
+            let (u, v) = match adjacent_edge {
+                EdgeId::Bottom => (along, past),
+                EdgeId::Right => (ures - 1 - past, along),
+                EdgeId::Top => (ures - 1 - along, vres - 1 - past),
+                EdgeId::Left => (past, vres - 1 - along),
+            };
+            texel = TexelLocation {
+                face_id: adjacent_face,
+                u,
+                v,
+            };
+            face_info = adjacent_info;
+        }
+
+        Err(Error::Message(format!(
+            "ptex: texel ({u}, {v}) is too far outside of face {face_id} in {:?}",
+            self.filename()
+        )))
+    }
+
+    /// Read the texel data for an entire face into a newly allocated buffer.
+    ///
+    /// The data is returned at the face's highest resolution, channel-interleaved
+    /// per texel and stored in v-major order. The element type must match the
+    /// Texture's [`DataType`].
+    pub fn face_data<T: Texel>(&self, face_id: i32) -> Result<Vec<T>, Error> {
+        let res = self.face_info(face_id)?.resolution();
+        let mut buf = vec![T::default(); res.size() * self.num_channels() as usize];
+        self.read_face_into(face_id, &mut buf, 0)?;
+        Ok(buf)
+    }
+
+    /// Read the texel data for an entire face into a caller-provided buffer.
+    ///
+    /// The data is written at the face's highest resolution, channel-interleaved
+    /// per texel and stored in v-major order.
+    ///
+    /// Parameters:
+    /// - face_id: Face index [0..num_faces-1].
+    /// - buf: Destination buffer. The element type must match the Texture's DataType.
+    /// - stride: Distance between rows, in bytes (if zero, data is written packed).
+    pub fn read_face_into<T: Texel>(
+        &self,
+        face_id: i32,
+        buf: &mut [T],
+        stride: i32,
+    ) -> Result<(), Error> {
+        let res = self.face_info(face_id)?.resolution();
+        self.check_face_buffer(res, buf, stride)?;
+        let ((), messages) = self.collect_errors(|| unsafe {
+            sys::ptextexture_get_data(self.ptr, face_id, buf.as_mut_ptr() as *mut u8, stride);
+        });
+        check_messages(&format!("ptex: reading face {face_id} failed"), messages)
+    }
+
+    /// Read the texel data for a face at a reduced resolution into a newly allocated buffer.
+    ///
+    /// Reductions are read from the file's mipmaps when present and are generated
+    /// on the fly otherwise. The requested resolution must not be larger than the
+    /// face's stored resolution in either direction.
+    pub fn face_data_at_res<T: Texel>(&self, face_id: i32, res: Res) -> Result<Vec<T>, Error> {
+        self.check_face_id(face_id)?;
+        self.check_face_res(face_id, res)?;
+        let mut buf = vec![T::default(); res.size() * self.num_channels() as usize];
+        self.read_face_into_at_res(face_id, res, &mut buf, 0)?;
+        Ok(buf)
+    }
+
+    /// Read the texel data for a face at a reduced resolution into a caller-provided buffer.
+    ///
+    /// Parameters:
+    /// - face_id: Face index [0..num_faces-1].
+    /// - res: Resolution to read. Must not exceed the face's stored resolution.
+    /// - buf: Destination buffer. The element type must match the Texture's DataType.
+    /// - stride: Distance between rows, in bytes (if zero, data is written packed).
+    pub fn read_face_into_at_res<T: Texel>(
+        &self,
+        face_id: i32,
+        res: Res,
+        buf: &mut [T],
+        stride: i32,
+    ) -> Result<(), Error> {
+        self.check_face_id(face_id)?;
+        self.check_face_res(face_id, res)?;
+        self.check_face_buffer(res, buf, stride)?;
+        let ((), messages) = self.collect_errors(|| unsafe {
+            sys::ptextexture_get_data_at_res(
+                self.ptr,
+                face_id,
+                buf.as_mut_ptr() as *mut u8,
+                stride,
+                res.into(),
+            );
+        });
+        check_messages(&format!("ptex: reading face {face_id} failed"), messages)
+    }
+
+    /// Run a Ptex operation and return the messages it reported on the current thread.
+    fn collect_errors<R>(&self, operation: impl FnOnce() -> R) -> (R, Vec<String>) {
+        collect_errors(self.errors.as_deref(), operation)
+    }
+
+    /// Ensure that a face id is in the range [0..num_faces-1].
+    fn check_face_id(&self, face_id: i32) -> Result<(), Error> {
+        if face_id < 0 || face_id >= self.num_faces() {
+            return Err(Error::FaceIdOutOfRange {
+                face_id,
+                num_faces: self.num_faces(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Ensure that a channel range is within [0..num_channels-1].
+    fn check_channels(&self, first_channel: i32, num_channels: i32) -> Result<(), Error> {
+        if first_channel < 0
+            || num_channels < 1
+            || first_channel + num_channels > self.num_channels()
+        {
+            return Err(Error::ChannelOutOfRange {
+                first_channel,
+                num_channels,
+                available: self.num_channels(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Ensure that a resolution is no larger than a face's stored resolution.
+    fn check_face_res(&self, face_id: i32, res: Res) -> Result<(), Error> {
+        let face_res = self.face_info(face_id)?.resolution();
+        if res.0.ulog2 < 0
+            || res.0.vlog2 < 0
+            || res.0.ulog2 > face_res.0.ulog2
+            || res.0.vlog2 > face_res.0.vlog2
+        {
+            return Err(Error::InvalidResolution {
+                face_id,
+                res,
+                face_res,
+            });
+        }
+        Ok(())
+    }
+
+    /// Ensure that a buffer can hold a face of the specified resolution.
+    fn check_face_buffer<T: Texel>(&self, res: Res, buf: &[T], stride: i32) -> Result<(), Error> {
+        if T::DATA_TYPE != self.data_type() {
+            return Err(Error::DataTypeMismatch {
+                expected: self.data_type(),
+                actual: T::DATA_TYPE,
+            });
+        }
+        let required = face_buffer_len(res, self.num_channels(), T::DATA_TYPE, stride)?;
+        if buf.len() < required {
+            return Err(Error::BufferTooSmall {
+                required,
+                actual: buf.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The location of a texel returned by [`Texture::resolve_texel()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TexelLocation {
+    /// Face that contains the texel.
+    pub face_id: i32,
+    /// U coordinate [0..ures-1].
+    pub u: i32,
+    /// V coordinate [0..vres-1].
+    pub v: i32,
+}
+
+/// Return the number of values required to hold a face of the specified resolution.
+///
+/// A stride of zero means that rows are packed. Otherwise the stride is the distance
+/// between rows in bytes and must be a multiple of the value size that is large enough
+/// to hold a row.
+pub(crate) fn face_buffer_len(
+    res: Res,
+    num_channels: i32,
+    data_type: DataType,
+    stride: i32,
+) -> Result<usize, Error> {
+    let value_size = DataSize::get(data_type) as usize;
+    let row_len = res.u() as usize * num_channels.max(0) as usize;
+    if stride == 0 {
+        return Ok(row_len * res.v() as usize);
+    }
+    let row_size = row_len * value_size;
+    let invalid_stride = Error::InvalidStride {
+        stride,
+        row_size,
+        value_size,
+    };
+    let stride = usize::try_from(stride).map_err(|_| invalid_stride.clone())?;
+    if stride < row_size || stride % value_size != 0 {
+        return Err(invalid_stride);
     }
+    Ok((res.v() as usize - 1) * (stride / value_size) + row_len)
 }
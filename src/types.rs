@@ -72,6 +72,27 @@ impl From<DataType> for ptex_sys::DataType {
     }
 }
 
+/// Element types that can be used to store texel data.
+///
+/// Each Texel type corresponds to exactly one [`DataType`].
+pub trait Texel: Copy + Default {
+    /// The DataType stored by this texel type.
+    const DATA_TYPE: DataType;
+}
+
+macro_rules! texel_impl {
+    ($typ:ty, $variant:path) => {
+        impl Texel for $typ {
+            const DATA_TYPE: DataType = $variant;
+        }
+    };
+}
+
+texel_impl!(u8, DataType::UInt8);
+texel_impl!(u16, DataType::UInt16);
+texel_impl!(half::f16, DataType::Float16);
+texel_impl!(f32, DataType::Float32);
+
 /// How to handle transformation across edges when filtering.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum EdgeFilterMode {
@@ -102,6 +123,60 @@ impl From<EdgeFilterMode> for ptex_sys::EdgeFilterMode {
     }
 }
 
+/// Filter types supported by [`Filter`](crate::Filter).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FilterType {
+    /// Point-sampled (no filtering).
+    Point,
+    /// Bi-linear interpolation.
+    Bilinear,
+    /// Box filter.
+    Box,
+    /// Gaussian filter.
+    Gaussian,
+    /// General bi-cubic filter (uses sharpness option).
+    Bicubic,
+    /// BSpline (equivalent to bi-cubic w/ sharpness=0).
+    BSpline,
+    /// Catmull-Rom (equivalent to bi-cubic w/ sharpness=1).
+    CatmullRom,
+    /// Mitchell (equivalent to bi-cubic w/ sharpness=2/3).
+    Mitchell,
+}
+
+/// Convert ptex_sys::FilterType into FilterType.
+impl From<ptex_sys::FilterType> for FilterType {
+    fn from(filter_type: ptex_sys::FilterType) -> FilterType {
+        match filter_type {
+            ptex_sys::FilterType::Point => FilterType::Point,
+            ptex_sys::FilterType::Bilinear => FilterType::Bilinear,
+            ptex_sys::FilterType::Box => FilterType::Box,
+            ptex_sys::FilterType::Gaussian => FilterType::Gaussian,
+            ptex_sys::FilterType::Bicubic => FilterType::Bicubic,
+            ptex_sys::FilterType::BSpline => FilterType::BSpline,
+            ptex_sys::FilterType::CatmullRom => FilterType::CatmullRom,
+            ptex_sys::FilterType::Mitchell => FilterType::Mitchell,
+            _ => FilterType::Box,
+        }
+    }
+}
+
+/// Convert FilterType into ptex_sys::FilterType.
+impl From<FilterType> for ptex_sys::FilterType {
+    fn from(filter_type: FilterType) -> ptex_sys::FilterType {
+        match filter_type {
+            FilterType::Point => ptex_sys::FilterType::Point,
+            FilterType::Bilinear => ptex_sys::FilterType::Bilinear,
+            FilterType::Box => ptex_sys::FilterType::Box,
+            FilterType::Gaussian => ptex_sys::FilterType::Gaussian,
+            FilterType::Bicubic => ptex_sys::FilterType::Bicubic,
+            FilterType::BSpline => ptex_sys::FilterType::BSpline,
+            FilterType::CatmullRom => ptex_sys::FilterType::CatmullRom,
+            FilterType::Mitchell => ptex_sys::FilterType::Mitchell,
+        }
+    }
+}
+
 /// Edge IDs used in adjacency data in the Ptex::FaceInfo struct.
 /// Edge ID usage for triangle meshes is TBD.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -116,6 +191,10 @@ pub enum EdgeId {
     Left,
 }
 
+/// Edge ids indexed by their position in the FaceInfo adjacency arrays, which is also
+/// the order that edges are visited around a face.
+pub(crate) const EDGE_IDS: [EdgeId; 4] = [EdgeId::Bottom, EdgeId::Right, EdgeId::Top, EdgeId::Left];
+
 /// Convert ptex_sys::EdgeId into EdgeId.
 impl From<ptex_sys::EdgeId> for EdgeId {
     fn from(edge_id: ptex_sys::EdgeId) -> EdgeId {
@@ -223,7 +302,7 @@ impl From<MetaDataType> for ptex_sys::MetaDataType {
 /// The resolution is stored in log form: ulog2 = log2(ures), vlog2 = log2(vres)).
 /// Note: negative ulog2 or vlog2 values are reserved for internal use.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub struct Res(sys::Res);
+pub struct Res(pub(crate) sys::Res);
 
 impl Res {
     pub fn from_uv(u: i8, v: i8) -> Self {
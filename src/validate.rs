@@ -0,0 +1,245 @@
+use crate::types::EDGE_IDS;
+use crate::{EdgeId, Error, FaceInfo, MeshType, Res, Texture};
+
+/// An adjacency problem found by [`AdjacencyReport::from_texture()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdjacencyViolation {
+    /// An adjacent face id is not -1 and not in the range [0..num_faces-1].
+    FaceIdOutOfRange {
+        /// Face that stores the adjacency.
+        face_id: i32,
+        /// Edge of the face.
+        edge: EdgeId,
+        /// Stored adjacent face id.
+        adjacent_face: i32,
+    },
+    /// A triangle face refers to the fourth edge of its neighbor.
+    InvalidTriangleEdge {
+        /// Face that stores the adjacency.
+        face_id: i32,
+        /// Edge of the face.
+        edge: EdgeId,
+        /// Adjacent face.
+        adjacent_face: i32,
+    },
+    /// The adjacent face does not point back across the recorded adjacent edge.
+    NotReciprocal {
+        /// Face that stores the adjacency.
+        face_id: i32,
+        /// Edge of the face.
+        edge: EdgeId,
+        /// Adjacent face.
+        adjacent_face: i32,
+        /// Edge of the adjacent face that should point back to face_id.
+        adjacent_edge: EdgeId,
+        /// Face stored by the adjacent face across adjacent_edge.
+        actual_face: i32,
+        /// Edge stored by the adjacent face across adjacent_edge.
+        actual_edge: EdgeId,
+    },
+    /// An interior edge of a subface is adjacent to a face that is not a subface.
+    SubfaceMismatch {
+        /// Subface that stores the adjacency.
+        face_id: i32,
+        /// Interior edge of the subface.
+        edge: EdgeId,
+        /// Adjacent face that is not a subface.
+        adjacent_face: i32,
+    },
+    /// Subfaces of the same polygon have different resolutions.
+    ResolutionMismatch {
+        /// Subface that stores the adjacency.
+        face_id: i32,
+        /// Interior edge of the subface.
+        edge: EdgeId,
+        /// Adjacent subface.
+        adjacent_face: i32,
+        /// Resolution of the subface.
+        res: Res,
+        /// Resolution of the adjacent subface.
+        adjacent_res: Res,
+    },
+    /// A triangle face does not have a square resolution.
+    NonSquareTriangle {
+        /// Triangle face.
+        face_id: i32,
+        /// Resolution of the face.
+        res: Res,
+    },
+    /// A face in a triangle mesh is marked as a subface.
+    TriangleSubface {
+        /// Triangle face.
+        face_id: i32,
+    },
+}
+
+/// The result of checking the adjacency of every face in a [`Texture`].
+///
+/// The following are reported as violations:
+/// - Adjacent face ids that are out of range.
+/// - Faces that are not pointed back to by their neighbors. If face A is adjacent to
+///   face B across edge e, then B must be adjacent to A across the adjacent edge that
+///   A records for e. A subface that borders a regular face may instead be represented
+///   by the other subface of its pair, which must point back to the regular face.
+/// - Interior edges of subfaces (the right and top edges) that are adjacent to regular
+///   faces, and subfaces of the same polygon with different resolutions.
+/// - Triangle faces that are not square or that are marked as subfaces.
+///
+/// ```no_run
+/// # fn main() -> Result<(), ptex::Error> {
+/// let cache = ptex::Cache::new(0, 0, false);
+/// let texture = cache.get("asset.ptx")?;
+/// let report = ptex::AdjacencyReport::from_texture(&texture)?;
+/// for violation in report.violations() {
+///     eprintln!("{violation:?}");
+/// }
+/// assert!(report.is_valid());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AdjacencyReport {
+    num_faces: i32,
+    violations: Vec<AdjacencyViolation>,
+}
+
+impl AdjacencyReport {
+    /// Check the adjacency of every face in a texture.
+    pub fn from_texture(texture: &Texture<'_>) -> Result<Self, Error> {
+        let num_faces = texture.num_faces();
+        let face_infos = (0..num_faces)
+            .map(|face_id| texture.face_info(face_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut report = Self {
+            num_faces,
+            violations: Vec::new(),
+        };
+        let is_triangle = texture.mesh_type() == MeshType::Triangle;
+        for (face_id, face_info) in face_infos.iter().enumerate() {
+            if is_triangle {
+                report.check_triangle(face_id as i32, face_info);
+            }
+            let num_edges = if is_triangle { 3 } else { 4 };
+            for edge in 0..num_edges {
+                report.check_edge(&face_infos, face_id, edge, is_triangle);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Return the number of faces that were checked.
+    pub fn num_faces(&self) -> i32 {
+        self.num_faces
+    }
+
+    /// Return true if no violations were found.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Return every violation, ordered by face id and edge.
+    pub fn violations(&self) -> &[AdjacencyViolation] {
+        &self.violations
+    }
+
+    /// Check the resolution and subface flag of a triangle face.
+    fn check_triangle(&mut self, face_id: i32, face_info: &FaceInfo) {
+        let res = face_info.resolution();
+        if res.u() != res.v() {
+            self.violations
+                .push(AdjacencyViolation::NonSquareTriangle { face_id, res });
+        }
+        if face_info.is_subface() {
+            self.violations
+                .push(AdjacencyViolation::TriangleSubface { face_id });
+        }
+    }
+
+    /// Check the adjacency across a single edge of a face.
+    fn check_edge(
+        &mut self,
+        face_infos: &[FaceInfo],
+        face_id: usize,
+        edge: usize,
+        is_triangle: bool,
+    ) {
+        let face_info = &face_infos[face_id];
+        let edge_id = EDGE_IDS[edge];
+        let adjacent_face = face_info.adjacent_face(edge as i32);
+        if adjacent_face == -1 {
+            return;
+        }
+        let Some(adjacent_info) = usize::try_from(adjacent_face)
+            .ok()
+            .and_then(|adjacent_face| face_infos.get(adjacent_face))
+        else {
+            self.violations.push(AdjacencyViolation::FaceIdOutOfRange {
+                face_id: face_id as i32,
+                edge: edge_id,
+                adjacent_face,
+            });
+            return;
+        };
+        let adjacent_edge = face_info.adjacent_edge(edge as i32);
+        if is_triangle && adjacent_edge == EdgeId::Left {
+            self.violations
+                .push(AdjacencyViolation::InvalidTriangleEdge {
+                    face_id: face_id as i32,
+                    edge: edge_id,
+                    adjacent_face,
+                });
+            return;
+        }
+
+        // The right and top edges of a subface are shared with the polygon's other subfaces.
+        let is_interior = matches!(edge_id, EdgeId::Right | EdgeId::Top);
+        if face_info.is_subface() && is_interior {
+            if !adjacent_info.is_subface() {
+                self.violations.push(AdjacencyViolation::SubfaceMismatch {
+                    face_id: face_id as i32,
+                    edge: edge_id,
+                    adjacent_face,
+                });
+            } else if adjacent_info.resolution() != face_info.resolution() {
+                self.violations
+                    .push(AdjacencyViolation::ResolutionMismatch {
+                        face_id: face_id as i32,
+                        edge: edge_id,
+                        adjacent_face,
+                        res: face_info.resolution(),
+                        adjacent_res: adjacent_info.resolution(),
+                    });
+            }
+        }
+
+        let actual_face = adjacent_info.adjacent_face(adjacent_edge as i32);
+        let actual_edge = adjacent_info.adjacent_edge(adjacent_edge as i32);
+        if actual_face == face_id as i32 && actual_edge == edge_id {
+            return;
+        }
+        // A regular face points to only one subface of the pair that borders it.
+        if face_info.is_subface() && !adjacent_info.is_subface() {
+            let is_pair = usize::try_from(actual_face)
+                .ok()
+                .and_then(|actual_face| face_infos.get(actual_face))
+                .is_some_and(|actual_info| {
+                    actual_info.is_subface()
+                        && actual_info.adjacent_face(actual_edge as i32) == adjacent_face
+                        && actual_info.adjacent_edge(actual_edge as i32) == adjacent_edge
+                });
+            if is_pair {
+                return;
+            }
+        }
+        self.violations.push(AdjacencyViolation::NotReciprocal {
+            face_id: face_id as i32,
+            edge: edge_id,
+            adjacent_face,
+            adjacent_edge,
+            actual_face,
+            actual_edge,
+        });
+    }
+}
@@ -1,7 +1,18 @@
 use crate::error::Error;
-use crate::{f16, sys, DataType, FaceInfo, MeshType, MetaDataType};
+use crate::texture::face_buffer_len;
+use crate::{
+    f16, sys, BorderMode, DataSize, DataType, EdgeFilterMode, FaceInfo, MeshType, MetaDataType,
+    Texel,
+};
 use cxx::let_cxx_string;
 use std::ffi::CStr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Interval between progress reports from [`Writer::close_with_progress()`].
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Interface for writing data to a ptex file.
 ///
@@ -15,12 +26,71 @@ use std::ffi::CStr;
 /// the data as-is.  The only reason to store unmultiplied-alpha
 /// textures in the file is to preserve the original texture data for
 /// later editing.
-pub struct Writer(pub(crate) *mut sys::PtexWriter);
+///
+/// # Closing
+///
+/// A file is only complete once [`Writer::close()`] has returned `Ok`. Ptex has no way
+/// to abandon a file that is being written, so dropping a Writer that has not been
+/// closed still lets Ptex finish writing, and then:
+///
+/// - Files created with [`Writer::new()`] are deleted, so that a partially-written
+///   texture is never mistaken for a finished one. A file that already existed when
+///   the Writer was opened is not deleted, and is replaced by whatever Ptex wrote.
+/// - Files opened with [`Writer::edit()`] are left with whatever edits Ptex applied.
+///
+/// In debug builds a warning is printed to stderr when an unclosed Writer is dropped.
+///
+/// Writers opened with [`WriterOptions::atomic()`] write to a temporary file next to the
+/// destination, which is renamed over the destination only after closing succeeds. The
+/// temporary file is removed if closing fails or if the Writer is dropped, leaving the
+/// destination untouched.
+///
+/// Closing consumes the Writer, so a file cannot be closed twice:
+///
+/// ```compile_fail
+/// let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 1)
+///     .open("texture.ptx")
+///     .unwrap();
+/// writer.close().unwrap();
+/// writer.close().unwrap();
+/// ```
+pub struct Writer {
+    ptr: *mut sys::PtexWriter,
+    /// Path to the file being written.
+    path: PathBuf,
+    /// Temporary file written in place of path and renamed over it on close.
+    temp_path: Option<PathBuf>,
+    /// Flush the file to disk before close() returns.
+    sync: bool,
+    /// Type of mesh for which the textures are defined.
+    mesh_type: MeshType,
+    /// Type of data stored in the file.
+    data_type: DataType,
+    /// Number of data channels.
+    num_channels: i32,
+    /// Number of faces in the file.
+    num_faces: i32,
+    /// Store uniform faces passed to write_face() as constant faces.
+    detect_constant_faces: bool,
+    /// The file was created by Writer::new() and is removed unless it is closed successfully.
+    created: bool,
+    /// close() has been called.
+    closed: bool,
+}
 
 impl Drop for Writer {
     fn drop(&mut self) {
+        if !self.closed && cfg!(debug_assertions) {
+            eprintln!(
+                "ptex: Writer for {:?} dropped without calling close()",
+                self.path
+            );
+        }
         unsafe {
-            sys::ptexwriter_release(self.0);
+            sys::ptexwriter_release(self.ptr);
+        }
+        if !self.closed {
+            self.discard();
         }
     }
 }
@@ -30,8 +100,14 @@ pub trait AsUInt8Ptr {
     fn as_u8_ptr(&self) -> *const u8;
 }
 
-// write_face() accepts a buffer that must be convertable to char*.
-pub trait AsFaceData: AsUInt8Ptr {}
+// write_face() accepts a buffer that:
+//
+// 1. Must be convertable to char*.
+// 2. Must have a data type and length that are validated before writing.
+pub trait AsFaceData: AsUInt8Ptr {
+    fn face_data_type(&self) -> DataType;
+    fn face_data_len(&self) -> usize;
+}
 
 // write_meta_data() accepts a buffer that:
 //
@@ -53,8 +129,15 @@ macro_rules! as_u8_ptr_impl {
 }
 
 macro_rules! as_face_data_impl {
-    ($typ:ty) => {
-        impl AsFaceData for $typ {}
+    ($typ:ty, $variant:path) => {
+        impl AsFaceData for $typ {
+            fn face_data_type(&self) -> DataType {
+                $variant
+            }
+            fn face_data_len(&self) -> usize {
+                self.len()
+            }
+        }
     };
 }
 
@@ -72,29 +155,29 @@ macro_rules! as_meta_data_impl {
 }
 
 as_u8_ptr_impl!(&[u8]);
-as_face_data_impl!(&[u8]);
+as_face_data_impl!(&[u8], DataType::UInt8);
 
 as_u8_ptr_impl!(&[u16]);
-as_face_data_impl!(&[u16]);
+as_face_data_impl!(&[u16], DataType::UInt16);
 
 as_u8_ptr_impl!(&[f16]);
-as_face_data_impl!(&[f16]);
+as_face_data_impl!(&[f16], DataType::Float16);
 
 as_u8_ptr_impl!(&[f32]);
-as_face_data_impl!(&[f32]);
+as_face_data_impl!(&[f32], DataType::Float32);
 as_meta_data_impl!(&[f32], MetaDataType::Float);
 
 as_u8_ptr_impl!(Vec<u8>);
-as_face_data_impl!(Vec<u8>);
+as_face_data_impl!(Vec<u8>, DataType::UInt8);
 
 as_u8_ptr_impl!(Vec<u16>);
-as_face_data_impl!(Vec<u16>);
+as_face_data_impl!(Vec<u16>, DataType::UInt16);
 
 as_u8_ptr_impl!(Vec<f16>);
-as_face_data_impl!(Vec<f16>);
+as_face_data_impl!(Vec<f16>, DataType::Float16);
 
 as_u8_ptr_impl!(Vec<f32>);
-as_face_data_impl!(Vec<f32>);
+as_face_data_impl!(Vec<f32>, DataType::Float32);
 as_meta_data_impl!(Vec<f32>, MetaDataType::Float);
 
 as_u8_ptr_impl!(&[i8]);
@@ -131,6 +214,210 @@ impl AsUInt8Ptr for str {
 }
 as_meta_data_impl!(str, MetaDataType::String);
 
+/// Header options used to create a [`Writer`].
+///
+/// The options describe the full header of a ptex file and are applied when the
+/// file is opened with [`WriterOptions::open()`] or [`WriterOptions::edit()`].
+///
+/// ```no_run
+/// # fn main() -> Result<(), ptex::Error> {
+/// let writer = ptex::WriterOptions::new(ptex::DataType::Float32, 4, 128)
+///     .alpha_channel(3)
+///     .generate_mipmaps(true)
+///     .border_modes(ptex::BorderMode::Periodic, ptex::BorderMode::Clamp)
+///     .open("texture.ptx")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WriterOptions {
+    mesh_type: MeshType,
+    data_type: DataType,
+    num_channels: i32,
+    alpha_channel: i32,
+    num_faces: i32,
+    generate_mipmaps: bool,
+    border_mode_u: BorderMode,
+    border_mode_v: BorderMode,
+    edge_filter_mode: EdgeFilterMode,
+    atomic: bool,
+    sync: bool,
+}
+
+impl WriterOptions {
+    /// Create options for a quad mesh with the specified data type, channels and faces.
+    ///
+    /// By default there is no alpha channel, mipmaps are not generated, both border
+    /// modes are [`BorderMode::Clamp`] and the edge filter mode is [`EdgeFilterMode::None`].
+    pub fn new(data_type: DataType, num_channels: i32, num_faces: i32) -> Self {
+        Self {
+            mesh_type: MeshType::Quad,
+            data_type,
+            num_channels,
+            alpha_channel: -1,
+            num_faces,
+            generate_mipmaps: false,
+            border_mode_u: BorderMode::Clamp,
+            border_mode_v: BorderMode::Clamp,
+            edge_filter_mode: EdgeFilterMode::None,
+            atomic: false,
+            sync: false,
+        }
+    }
+
+    /// Set the type of mesh for which the textures are defined.
+    pub fn mesh_type(mut self, mesh_type: MeshType) -> Self {
+        self.mesh_type = mesh_type;
+        self
+    }
+
+    /// Set the type of data stored within the file.
+    pub fn data_type(mut self, data_type: DataType) -> Self {
+        self.data_type = data_type;
+        self
+    }
+
+    /// Set the number of data channels.
+    pub fn num_channels(mut self, num_channels: i32) -> Self {
+        self.num_channels = num_channels;
+        self
+    }
+
+    /// Set the index of the alpha channel [0..num_channels-1], or -1 if there is no alpha channel.
+    pub fn alpha_channel(mut self, alpha_channel: i32) -> Self {
+        self.alpha_channel = alpha_channel;
+        self
+    }
+
+    /// Set the number of faces in the mesh.
+    pub fn num_faces(mut self, num_faces: i32) -> Self {
+        self.num_faces = num_faces;
+        self
+    }
+
+    /// Generate mipmaps when the file is closed.
+    pub fn generate_mipmaps(mut self, generate_mipmaps: bool) -> Self {
+        self.generate_mipmaps = generate_mipmaps;
+        self
+    }
+
+    /// Set the border modes in the u and v directions.
+    pub fn border_modes(mut self, border_mode_u: BorderMode, border_mode_v: BorderMode) -> Self {
+        self.border_mode_u = border_mode_u;
+        self.border_mode_v = border_mode_v;
+        self
+    }
+
+    /// Set the edge filter mode.
+    pub fn edge_filter_mode(mut self, edge_filter_mode: EdgeFilterMode) -> Self {
+        self.edge_filter_mode = edge_filter_mode;
+        self
+    }
+
+    /// Write to a temporary file that replaces the destination when the Writer is closed.
+    ///
+    /// The temporary file is created in the same directory as the destination so that it
+    /// can be renamed atomically. Readers never observe a partially-written file: the
+    /// destination either keeps its previous contents or holds the complete new file.
+    /// When editing, the existing file is copied to the temporary file before it is opened.
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Flush the file to disk before [`Writer::close()`] returns.
+    ///
+    /// For atomic writes the temporary file is flushed before it is renamed and, on Unix,
+    /// the containing directory is flushed after the rename.
+    pub fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Open a new texture file for writing. See [`Writer::new()`].
+    pub fn open<P: AsRef<std::path::Path>>(&self, filename: P) -> Result<Writer, Error> {
+        let filename = filename.as_ref();
+        let temp_path = self.atomic.then(|| temp_path_for(filename));
+        let writer = Writer::new(
+            temp_path.as_deref().unwrap_or(filename),
+            self.mesh_type,
+            self.data_type,
+            self.num_channels,
+            self.alpha_channel,
+            self.num_faces,
+            self.generate_mipmaps,
+        );
+        self.apply(writer, filename, temp_path)
+    }
+
+    /// Open an existing texture file for editing. See [`Writer::edit()`].
+    ///
+    /// The border modes and edge filter mode replace the values stored in the file.
+    pub fn edit<P: AsRef<std::path::Path>>(
+        &self,
+        filename: P,
+        incremental: bool,
+    ) -> Result<Writer, Error> {
+        let filename = filename.as_ref();
+        let temp_path = self.atomic.then(|| temp_path_for(filename));
+        if let Some(temp_path) = &temp_path {
+            if filename.exists() {
+                if let Err(err) = std::fs::copy(filename, temp_path) {
+                    let _ = std::fs::remove_file(temp_path);
+                    return Err(Error::WriteFailed(filename.to_path_buf(), err.to_string()));
+                }
+            }
+        }
+        let writer = Writer::edit(
+            temp_path.as_deref().unwrap_or(filename),
+            incremental,
+            self.mesh_type,
+            self.data_type,
+            self.num_channels,
+            self.alpha_channel,
+            self.num_faces,
+            self.generate_mipmaps,
+        );
+        self.apply(writer, filename, temp_path)
+    }
+
+    /// Apply the options that are not part of the Writer constructors.
+    fn apply(
+        &self,
+        writer: Result<Writer, Error>,
+        filename: &std::path::Path,
+        temp_path: Option<PathBuf>,
+    ) -> Result<Writer, Error> {
+        let mut writer = writer.map_err(|err| {
+            let Some(temp_path) = &temp_path else {
+                return err;
+            };
+            remove_written_files(temp_path);
+            match err {
+                Error::WriteFailed(_, message) => {
+                    Error::WriteFailed(filename.to_path_buf(), message)
+                }
+                err => err,
+            }
+        })?;
+        unsafe {
+            sys::ptexwriter_set_border_modes(
+                writer.ptr,
+                self.border_mode_u.into(),
+                self.border_mode_v.into(),
+            );
+            sys::ptexwriter_set_edge_filter_mode(writer.ptr, self.edge_filter_mode.into());
+        }
+        if temp_path.is_some() {
+            writer.path = filename.to_path_buf();
+            writer.temp_path = temp_path;
+        }
+        writer.sync = self.sync;
+
+        Ok(writer)
+    }
+}
+
 impl Writer {
     /// Open a new texture file for writing.
     ///
@@ -142,6 +429,8 @@ impl Writer {
     /// - alpha_channel: alphachan Index of alpha channel, [0..nchannels-1] or -1 if no alpha channel is present.
     /// - num_faces: nfaces Number of faces in mesh.
     /// - genmipmaps: Specify true if mipmaps should be generated.
+    ///
+    /// See [`WriterOptions`] for configuring border modes and the edge filter mode.
     pub fn new(
         filename: &std::path::Path,
         mesh_type: MeshType,
@@ -153,6 +442,7 @@ impl Writer {
     ) -> Result<Self, Error> {
         let_cxx_string!(error_str = "");
         let filename_str = filename.to_str().unwrap_or_default();
+        let created = !filename.exists();
         let writer = unsafe {
             sys::ptexwriter_open(
                 filename_str,
@@ -170,30 +460,270 @@ impl Writer {
             )
         };
 
+        let mut writer = Self::from_raw(
+            writer,
+            &error_str,
+            filename,
+            mesh_type,
+            data_type,
+            num_channels,
+            num_faces,
+            "Writer::new",
+        )?;
+        writer.created = created;
+
+        Ok(writer)
+    }
+
+    /// Open an existing texture file for editing.
+    ///
+    /// If the incremental parameter is true, edits are appended to the file as a series of
+    /// edit blocks. This is fast because only the edited faces are written, but readers must
+    /// apply the edits when the file is read. Otherwise the file is rewritten on close with
+    /// the edited faces replacing the existing faces. Faces that are not written are
+    /// preserved in either mode.
+    ///
+    /// The header parameters must match the existing file. If the file does not exist,
+    /// a new file is created in its place.
+    ///
+    /// Parameters:
+    /// - filename: Path to file.
+    /// - incremental: Append edit blocks instead of rewriting the file.
+    /// - See [`Writer::new()`] for a description of the other parameters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn edit(
+        filename: &std::path::Path,
+        incremental: bool,
+        mesh_type: MeshType,
+        data_type: DataType,
+        num_channels: i32,
+        alpha_channel: i32,
+        num_faces: i32,
+        generate_mipmaps: bool,
+    ) -> Result<Self, Error> {
+        let_cxx_string!(error_str = "");
+        let filename_str = filename.to_str().unwrap_or_default();
+        let writer = unsafe {
+            sys::ptexwriter_edit(
+                filename_str,
+                incremental,
+                mesh_type.into(),
+                data_type.into(),
+                num_channels,
+                alpha_channel,
+                num_faces,
+                generate_mipmaps,
+                error_str.as_mut().get_unchecked_mut(),
+            )
+        };
+
+        Self::from_raw(
+            writer,
+            &error_str,
+            filename,
+            mesh_type,
+            data_type,
+            num_channels,
+            num_faces,
+            "Writer::edit",
+        )
+    }
+
+    /// Wrap a PtexWriter pointer returned by ptexwriter_open() or ptexwriter_edit().
+    #[allow(clippy::too_many_arguments)]
+    fn from_raw(
+        writer: *mut sys::PtexWriter,
+        error_str: &cxx::CxxString,
+        filename: &std::path::Path,
+        mesh_type: MeshType,
+        data_type: DataType,
+        num_channels: i32,
+        num_faces: i32,
+        context: &str,
+    ) -> Result<Self, Error> {
         if writer.is_null() || !error_str.is_empty() {
+            unsafe {
+                sys::ptexwriter_release(writer);
+            }
             let error_message = if error_str.is_empty() {
-                format!("ptex: Writer::new({filename_str}) failed: {error_str}")
+                format!("ptex: {context}({filename:?}) failed")
             } else {
-                format!("ptex: Writer::new({filename_str}) failed")
+                error_str.to_string_lossy().trim_end().to_string()
             };
-            return Err(Error::FileIO(filename.to_path_buf(), error_message));
+            return Err(Error::WriteFailed(filename.to_path_buf(), error_message));
         }
 
-        Ok(Self(writer))
+        Ok(Self {
+            ptr: writer,
+            path: filename.to_path_buf(),
+            temp_path: None,
+            sync: false,
+            mesh_type,
+            data_type,
+            num_channels,
+            num_faces,
+            detect_constant_faces: false,
+            created: false,
+            closed: false,
+        })
     }
 
     /// Close the file.  This operation can take some time if mipmaps are being generated or if there
-    /// are many edit blocks.  If an error occurs while writing, false is returned and an error string
-    /// is written into the error parameter.
-    pub fn close(&mut self) -> Result<(), Error> {
-        let error_message = unsafe { sys::ptexwriter_close(self.0) };
-        if !error_message.is_empty() {
-            return Err(Error::Message(error_message));
+    /// are many edit blocks.  If an error occurs while writing, [`Error::WriteFailed`] is returned
+    /// with the error message reported by Ptex.
+    ///
+    /// The Writer is consumed and cannot be used after closing. If closing fails, a file created
+    /// with [`Writer::new()`] is deleted. For atomic writes the temporary file is renamed over
+    /// the destination after the file has been written.
+    pub fn close(mut self) -> Result<(), Error> {
+        let error_message = unsafe { sys::ptexwriter_close(self.ptr) };
+        self.finish_close(error_message)
+    }
+
+    /// Close the file while reporting progress and checking for cancellation.
+    ///
+    /// Ptex writes the file in a single blocking call that cannot be interrupted, so
+    /// the file is closed on a background thread while the calling thread invokes
+    /// `progress` when closing starts, every few milliseconds while Ptex is writing,
+    /// and once more when writing has finished.
+    ///
+    /// See [`CloseProgress`] for what is reported.
+    ///
+    /// `cancel` is checked when closing starts and before every progress report. Once
+    /// it is cancelled, [`Error::Cancelled`] is returned without waiting for Ptex. The
+    /// background thread lets Ptex finish, because Ptex cannot abandon a file, and then
+    /// removes the output, so the destination is left as it was before the Writer was
+    /// opened. The output may be left behind if the process exits before Ptex finishes.
+    ///
+    /// Only writers whose output can be removed are cancelled: writers opened with
+    /// [`WriterOptions::atomic()`] and files created by [`Writer::new()`]. Other writers
+    /// replace or edit an existing file that cannot be restored, so cancellation is
+    /// ignored for them and the result of closing the file is returned.
+    pub fn close_with_progress<F: FnMut(&CloseProgress)>(
+        mut self,
+        cancel: &CancellationToken,
+        mut progress: F,
+    ) -> Result<(), Error> {
+        let written_path = self.temp_path.clone().unwrap_or_else(|| self.path.clone());
+        let discard_path = self.discard_path().map(std::path::Path::to_path_buf);
+
+        let start = Instant::now();
+        let state = Arc::new(CloseState::default());
+        // The background thread owns the writer until the calling thread takes it back,
+        // and releases it if the calling thread stops waiting.
+        let ptr = std::mem::replace(&mut self.ptr, std::ptr::null_mut());
+        self.closed = true;
+        let writer = WriterPtr(ptr);
+        let thread_state = Arc::clone(&state);
+        let thread_discard_path = discard_path.clone();
+        std::thread::spawn(move || {
+            let error_message = unsafe { sys::ptexwriter_close(writer.get()) };
+            let mut status = thread_state.lock();
+            if matches!(*status, CloseStatus::Abandoned) {
+                drop(status);
+                unsafe { sys::ptexwriter_release(writer.get()) };
+                if let Some(discard_path) = &thread_discard_path {
+                    remove_written_files(discard_path);
+                }
+            } else {
+                *status = CloseStatus::Finished(error_message);
+                thread_state.finished.notify_all();
+            }
+        });
+
+        // Abandon the writer when cancelled or if the progress callback panics.
+        let abandon = AbandonClose {
+            state: &state,
+            ptr,
+            discard_path: discard_path.as_deref(),
+        };
+        let error_message = loop {
+            let mut status = state.lock();
+            if let CloseStatus::Finished(error_message) = &mut *status {
+                break std::mem::take(error_message);
+            }
+            drop(status);
+            if cancel.is_cancelled() && discard_path.is_some() {
+                return Err(Error::Cancelled(self.path.clone()));
+            }
+            progress(&CloseProgress {
+                bytes_written: bytes_written(&written_path),
+                elapsed: start.elapsed(),
+            });
+            let _ = state
+                .finished
+                .wait_timeout_while(state.lock(), PROGRESS_INTERVAL, |status| {
+                    matches!(status, CloseStatus::Running)
+                });
+        };
+        std::mem::forget(abandon);
+        self.ptr = ptr;
+
+        let result = self.finish_close(error_message);
+        if result.is_ok() {
+            progress(&CloseProgress {
+                bytes_written: std::fs::metadata(&self.path).map_or(0, |metadata| metadata.len()),
+                elapsed: start.elapsed(),
+            });
+        }
+
+        result
+    }
+
+    /// Complete closing the file once Ptex has returned.
+    fn finish_close(&mut self, error_message: String) -> Result<(), Error> {
+        self.closed = true;
+        let result = if error_message.is_empty() {
+            self.finish()
+                .map_err(|err| Error::WriteFailed(self.path.clone(), err.to_string()))
+        } else {
+            Err(Error::WriteFailed(self.path.clone(), error_message))
+        };
+        if result.is_err() {
+            self.discard();
         }
 
+        result
+    }
+
+    /// Flush and move the written file into place after Ptex has closed it.
+    fn finish(&self) -> std::io::Result<()> {
+        let written_path = self.temp_path.as_ref().unwrap_or(&self.path);
+        if self.sync {
+            std::fs::File::open(written_path)?.sync_all()?;
+        }
+        if let Some(temp_path) = &self.temp_path {
+            std::fs::rename(temp_path, &self.path)?;
+            #[cfg(unix)]
+            if self.sync {
+                if let Some(parent) = self.path.parent() {
+                    let parent = if parent.as_os_str().is_empty() {
+                        std::path::Path::new(".")
+                    } else {
+                        parent
+                    };
+                    std::fs::File::open(parent)?.sync_all()?;
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Remove the file that was being written when the Writer is not closed successfully.
+    fn discard(&self) {
+        if let Some(discard_path) = self.discard_path() {
+            remove_written_files(discard_path);
+        }
+    }
+
+    /// Return the file that is removed when the Writer is not closed successfully, if any.
+    fn discard_path(&self) -> Option<&std::path::Path> {
+        match &self.temp_path {
+            Some(temp_path) => Some(temp_path),
+            None => self.created.then_some(self.path.as_path()),
+        }
+    }
+
     /// Write u8/u16/f16/f32 texture data for a face.
     ///
     /// The data is assumed to be channel-interleaved per texel and stored in v-major order.
@@ -201,32 +731,345 @@ impl Writer {
     /// Parameters:
     /// - face_id: Face index [0..nfaces-1].
     /// - face_info: Face resolution and adjacency information.
-    /// - texel_buf: Texel data to write.
+    /// - texel_buf: Texel data to write. The element type must match the Writer's DataType.
     /// - stride: Distance between rows, in bytes (if zero, data is assumed packed).
     ///
-    /// If an error is encountered while writing, false is returned and an error message can be
-    /// retrieved when close is called.
+    /// The buffer is validated before it is passed to Ptex. [`Error::DataTypeMismatch`]
+    /// is returned when the element type does not match the Writer's DataType and
+    /// [`Error::BufferTooSmall`] is returned when the buffer cannot hold `res.size()`
+    /// texels of `num_channels` values each.
+    ///
+    /// If an error is encountered while writing, [`Error::WriteFailed`] is returned and the
+    /// full error message is returned when close is called.
     pub fn write_face<TexelBuf: AsFaceData>(
         &self,
         face_id: i32,
         face_info: &FaceInfo,
         texel_buf: &TexelBuf,
         stride: i32,
-    ) -> bool {
-        unsafe {
-            sys::ptexwriter_write_face(self.0, face_id, &face_info.0, texel_buf.as_u8_ptr(), stride)
+    ) -> Result<(), Error> {
+        self.check_face_id(face_id)?;
+        self.check_face_data(face_info, texel_buf, stride)?;
+        if self.detect_constant_faces && self.is_constant(face_info, texel_buf, stride) {
+            return self.write_constant_face_data(face_id, face_info, texel_buf.as_u8_ptr());
+        }
+        let ok = unsafe {
+            sys::ptexwriter_write_face(
+                self.ptr,
+                face_id,
+                &face_info.0,
+                texel_buf.as_u8_ptr(),
+                stride,
+            )
+        };
+        if !ok {
+            return Err(Error::WriteFailed(
+                self.path.clone(),
+                format!("ptex: Writer::write_face({face_id}) failed"),
+            ));
         }
+        Ok(())
     }
 
-    pub fn write_meta_data<DataBuf: AsMetaData>(&self, key: &CStr, buf: DataBuf) -> bool {
-        unsafe {
+    /// Write a meta data entry.
+    ///
+    /// [`Error::WriteFailed`] is returned when Ptex rejects the entry.
+    pub fn write_meta_data<DataBuf: AsMetaData>(
+        &self,
+        key: &CStr,
+        buf: DataBuf,
+    ) -> Result<(), Error> {
+        let ok = unsafe {
             sys::ptexwriter_write_meta_data(
-                self.0,
+                self.ptr,
                 key.as_ptr(),
                 buf.meta_data_type().into(),
                 buf.as_u8_ptr(),
                 buf.meta_data_len(),
             )
+        };
+        if !ok {
+            return Err(Error::WriteFailed(
+                self.path.clone(),
+                format!("ptex: Writer::write_meta_data({key:?}) failed"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write constant texture data for a face.
+    ///
+    /// The face is stored as a single texel value, which is more efficient than
+    /// writing a full face of identical texels. The face's resolution is still
+    /// recorded in the face info.
+    ///
+    /// Parameters:
+    /// - face_id: Face index [0..nfaces-1].
+    /// - face_info: Face resolution and adjacency information.
+    /// - texel: A single texel containing a value for each channel.
+    pub fn write_constant_face<T: Texel>(
+        &self,
+        face_id: i32,
+        face_info: &FaceInfo,
+        texel: &[T],
+    ) -> Result<(), Error> {
+        self.check_face_id(face_id)?;
+        if T::DATA_TYPE != self.data_type {
+            return Err(Error::DataTypeMismatch {
+                expected: self.data_type,
+                actual: T::DATA_TYPE,
+            });
+        }
+        if texel.len() < self.num_channels as usize {
+            return Err(Error::BufferTooSmall {
+                required: self.num_channels as usize,
+                actual: texel.len(),
+            });
+        }
+        self.write_constant_face_data(face_id, face_info, texel.as_ptr() as *const u8)
+    }
+
+    /// Enable or disable detection of constant faces in [`Writer::write_face()`].
+    ///
+    /// When enabled, buffers passed to write_face() whose texels are all identical
+    /// are written using [`Writer::write_constant_face()`]. Texels are compared
+    /// bitwise. Detection is disabled by default.
+    pub fn set_detect_constant_faces(&mut self, detect_constant_faces: bool) {
+        self.detect_constant_faces = detect_constant_faces;
+    }
+
+    /// Return true if write_face() detects and writes constant faces.
+    pub fn detect_constant_faces(&self) -> bool {
+        self.detect_constant_faces
+    }
+
+    /// Return the type of mesh for which the textures are defined.
+    pub fn mesh_type(&self) -> MeshType {
+        self.mesh_type
+    }
+
+    /// Return the number of faces in the file.
+    pub fn num_faces(&self) -> i32 {
+        self.num_faces
+    }
+
+    /// Write a single texel of data as a constant face.
+    fn write_constant_face_data(
+        &self,
+        face_id: i32,
+        face_info: &FaceInfo,
+        data: *const u8,
+    ) -> Result<(), Error> {
+        let ok =
+            unsafe { sys::ptexwriter_write_constant_face(self.ptr, face_id, &face_info.0, data) };
+        if !ok {
+            return Err(Error::WriteFailed(
+                self.path.clone(),
+                format!("ptex: Writer::write_constant_face({face_id}) failed"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return true if every texel in a face buffer is identical.
+    ///
+    /// The buffer must have been validated using check_face_data().
+    fn is_constant<TexelBuf: AsFaceData>(
+        &self,
+        face_info: &FaceInfo,
+        texel_buf: &TexelBuf,
+        stride: i32,
+    ) -> bool {
+        let res = face_info.resolution();
+        let value_size = DataSize::get(self.data_type) as usize;
+        let texel_size = value_size * self.num_channels as usize;
+        let row_size = res.u() as usize * texel_size;
+        let stride = if stride > 0 {
+            stride as usize
+        } else {
+            row_size
+        };
+        if texel_size == 0 || row_size == 0 {
+            return false;
+        }
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                texel_buf.as_u8_ptr(),
+                texel_buf.face_data_len() * value_size,
+            )
+        };
+        let first = &data[..texel_size];
+        (0..res.v() as usize).all(|row| {
+            let start = row * stride;
+            data[start..start + row_size]
+                .chunks_exact(texel_size)
+                .all(|texel| texel == first)
+        })
+    }
+
+    /// Ensure that a buffer matches the writer's data type and can hold a face.
+    fn check_face_data<TexelBuf: AsFaceData>(
+        &self,
+        face_info: &FaceInfo,
+        texel_buf: &TexelBuf,
+        stride: i32,
+    ) -> Result<(), Error> {
+        if texel_buf.face_data_type() != self.data_type {
+            return Err(Error::DataTypeMismatch {
+                expected: self.data_type,
+                actual: texel_buf.face_data_type(),
+            });
         }
+        let required = face_buffer_len(
+            face_info.resolution(),
+            self.num_channels,
+            self.data_type,
+            stride,
+        )?;
+        if texel_buf.face_data_len() < required {
+            return Err(Error::BufferTooSmall {
+                required,
+                actual: texel_buf.face_data_len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Ensure that a face id is in the range [0..num_faces-1].
+    fn check_face_id(&self, face_id: i32) -> Result<(), Error> {
+        if face_id < 0 || face_id >= self.num_faces {
+            return Err(Error::FaceIdOutOfRange {
+                face_id,
+                num_faces: self.num_faces,
+            });
+        }
+        Ok(())
     }
 }
+
+/// Return a unique temporary path in the same directory as a destination path.
+fn temp_path_for(filename: &std::path::Path) -> PathBuf {
+    static COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let name = filename.file_name().unwrap_or_default().to_string_lossy();
+    filename.with_file_name(format!(".{name}.{}.{count}.tmp", std::process::id()))
+}
+
+/// Remove a file being written and the file that Ptex writes next to it while closing.
+fn remove_written_files(path: &std::path::Path) {
+    let mut new_path = path.as_os_str().to_owned();
+    new_path.push(".new");
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(new_path);
+}
+
+/// Return the size of the output that Ptex has written for a file so far.
+fn bytes_written(path: &std::path::Path) -> u64 {
+    let mut new_path = path.as_os_str().to_owned();
+    new_path.push(".new");
+    std::fs::metadata(new_path)
+        .or_else(|_| std::fs::metadata(path))
+        .map_or(0, |metadata| metadata.len())
+}
+
+/// Progress reported by [`Writer::close_with_progress()`].
+///
+/// Ptex reduces faces and generates mipmaps inside a single call that does not report
+/// its progress, so the number of faces reduced cannot be provided. Progress is instead
+/// measured from the files that Ptex writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CloseProgress {
+    /// Estimated number of bytes written to the output file. This is a best-effort
+    /// measurement of the files Ptex writes and is exact only in the final report.
+    pub bytes_written: u64,
+    /// Time elapsed since closing started.
+    pub elapsed: Duration,
+}
+
+/// A token used to cancel [`Writer::close_with_progress()`] from another thread.
+///
+/// Clones share the same cancellation state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Return true if cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// A PtexWriter pointer that is closed on a background thread.
+struct WriterPtr(*mut sys::PtexWriter);
+
+/// The Writer is not used by the calling thread until the background thread has
+/// finished, and is released by the background thread if closing is abandoned.
+unsafe impl Send for WriterPtr {}
+
+impl WriterPtr {
+    fn get(&self) -> *mut sys::PtexWriter {
+        self.0
+    }
+}
+
+/// State shared with the background thread used by [`Writer::close_with_progress()`].
+#[derive(Default)]
+struct CloseState {
+    status: Mutex<CloseStatus>,
+    /// Notified when the status becomes [`CloseStatus::Finished`].
+    finished: Condvar,
+}
+
+impl CloseState {
+    fn lock(&self) -> MutexGuard<'_, CloseStatus> {
+        self.status.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Hands a PtexWriter that is being closed on a background thread back to that thread.
+///
+/// If the background thread has already finished, the writer is released and the
+/// output is removed here instead.
+struct AbandonClose<'a> {
+    state: &'a CloseState,
+    ptr: *mut sys::PtexWriter,
+    /// The output that is removed once Ptex has finished, if it can be removed.
+    discard_path: Option<&'a std::path::Path>,
+}
+
+impl Drop for AbandonClose<'_> {
+    fn drop(&mut self) {
+        let mut status = self.state.lock();
+        if matches!(*status, CloseStatus::Finished(_)) {
+            drop(status);
+            unsafe { sys::ptexwriter_release(self.ptr) };
+            if let Some(discard_path) = self.discard_path {
+                remove_written_files(discard_path);
+            }
+        } else {
+            *status = CloseStatus::Abandoned;
+        }
+    }
+}
+
+/// Progress of closing a PtexWriter on a background thread.
+#[derive(Default)]
+enum CloseStatus {
+    /// Ptex is writing the file.
+    #[default]
+    Running,
+    /// Ptex has returned with an error message, which is empty on success.
+    Finished(String),
+    /// The caller cancelled and no longer owns the writer.
+    Abandoned,
+}
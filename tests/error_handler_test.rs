@@ -0,0 +1,97 @@
+use anyhow::Result;
+
+use std::sync::{Arc, Mutex};
+
+/// Error handler that records every message it receives.
+#[derive(Clone, Default)]
+struct RecordingErrorHandler {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl ptex::ErrorHandler for RecordingErrorHandler {
+    fn report_error(&self, message: &str) {
+        self.messages.lock().unwrap().push(message.to_string());
+    }
+}
+
+/// Return the fixture truncated to half of its size so that face data reads fail.
+fn truncated_fixture() -> Result<Vec<u8>> {
+    let mut bytes = std::fs::read("tests/fixtures/test.ptx")?;
+    bytes.truncate(bytes.len() / 2);
+    Ok(bytes)
+}
+
+#[test]
+fn test_collected_errors_cache_get() -> Result<()> {
+    let cache = ptex::Cache::with_collected_errors(0, 0, false);
+    let result = cache.get("tests/fixtures/missing.ptx");
+    match result {
+        Err(ptex::Error::NotFound(path, message)) => {
+            assert!(path.ends_with("missing.ptx"));
+            assert!(!message.is_empty());
+        }
+        _ => panic!("expected an error for a missing file"),
+    }
+
+    // Errors do not affect subsequent successful reads.
+    let texture = cache.get("tests/fixtures/test.ptx")?;
+    let data: Vec<u16> = texture.face_data(0)?;
+    assert!(!data.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_error_handler_face_data() -> Result<()> {
+    let error_handler = RecordingErrorHandler::default();
+    let mut input_handler = ptex::MemoryInputHandler::new();
+    input_handler.insert("truncated.ptx", truncated_fixture()?);
+    let cache = ptex::Cache::with_handlers(
+        0,
+        0,
+        false,
+        Some(Box::new(input_handler)),
+        Some(Box::new(error_handler.clone())),
+    );
+
+    let texture = cache.get("truncated.ptx")?;
+    let failures = (0..texture.num_faces())
+        .filter(|face_id| texture.face_data::<u16>(*face_id).is_err())
+        .count();
+    assert!(failures > 0);
+
+    // Every collected error was also forwarded to the error handler.
+    let messages = error_handler.messages.lock().unwrap();
+    assert!(!messages.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_error_handler_cache_get() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/error_handler_cache_get.ptx");
+    std::fs::write(&filename, truncated_fixture()?)?;
+    let error_handler = RecordingErrorHandler::default();
+    let cache = ptex::Cache::with_error_handler(0, 0, false, error_handler.clone());
+    assert!(cache.get("tests/fixtures/missing.ptx").is_err());
+    assert!(cache.get("tests/fixtures/test.ptx").is_ok());
+
+    // Errors reading the truncated file are reported to the error handler.
+    let texture = cache.get(&filename)?;
+    let failures = (0..texture.num_faces())
+        .filter(|face_id| texture.face_data::<u16>(*face_id).is_err())
+        .count();
+    assert!(failures > 0);
+    {
+        let messages = error_handler.messages.lock().unwrap();
+        assert!(!messages.is_empty());
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("error_handler_cache_get.ptx")));
+    }
+    drop(texture);
+    drop(cache);
+    std::fs::remove_file(&filename)?;
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anyhow::Result;
+use assert_float_eq::{afe_is_f32_near, afe_near_error_msg, assert_f32_near};
+
+#[test]
+fn test_filter_point() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_channels = texture.num_channels();
+
+    let options = ptex::FilterOptions::new(ptex::FilterType::Point);
+    let mut filter = ptex::Filter::new(&texture, &options)?;
+
+    // Point-sampling at a texel center returns the texel value.
+    let res = texture.face_info(0)?.resolution();
+    let mut result = vec![0.0_f32; num_channels as usize];
+    for (u, v) in [(0, 1), (17, 33), (255, 127)] {
+        let uf = (u as f32 + 0.5) / res.u() as f32;
+        let vf = (v as f32 + 0.5) / res.v() as f32;
+        filter.eval(
+            &mut result,
+            0,
+            num_channels,
+            0,
+            uf,
+            vf,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            0.0,
+        )?;
+        for channel in 0..num_channels {
+            let pixel = texture.pixel_f32(0, u, v, channel, 1)?;
+            assert_f32_near!(result[channel as usize], pixel);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_types() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+
+    let filter_types = [
+        ptex::FilterType::Point,
+        ptex::FilterType::Bilinear,
+        ptex::FilterType::Box,
+        ptex::FilterType::Gaussian,
+        ptex::FilterType::Bicubic,
+        ptex::FilterType::BSpline,
+        ptex::FilterType::CatmullRom,
+        ptex::FilterType::Mitchell,
+    ];
+    for filter_type in filter_types {
+        let options = ptex::FilterOptions {
+            filter_type,
+            lerp: true,
+            sharpness: 0.5,
+            noedgeblend: false,
+        };
+        let mut filter = ptex::Filter::new(&texture, &options)?;
+        let mut result = [0.0_f32; 3];
+        filter.eval(&mut result, 0, 3, 4, 0.5, 0.5, 0.1, 0.0, 0.0, 0.1, 1.0, 0.0)?;
+        // Texel values lie within [0, 1], so a weighted average does too. Bicubic
+        // filters have negative lobes and overshoot near the edges of the checkerboard
+        // in the blue channel, so they are allowed a margin.
+        let range = match filter_type {
+            ptex::FilterType::Bicubic
+            | ptex::FilterType::CatmullRom
+            | ptex::FilterType::Mitchell => -0.5..=1.5,
+            _ => -1e-6..=1.0 + 1e-6,
+        };
+        for value in result {
+            assert!(range.contains(&value), "{filter_type:?}: {value}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_eval_buffer_too_small() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+
+    let mut filter = ptex::Filter::new(&texture, &ptex::FilterOptions::default())?;
+    let mut result = [0.0_f32; 2];
+    assert!(filter
+        .eval(&mut result, 0, 3, 0, 0.5, 0.5, 0.1, 0.0, 0.0, 0.1, 1.0, 0.0)
+        .is_err());
+    assert!(filter
+        .eval(&mut result, 0, 2, 0, 0.5, 0.5, 0.1, 0.0, 0.0, 0.1, 1.0, 0.0)
+        .is_ok());
+
+    Ok(())
+}
+
+/// Build a grid of lookups over every face of the test texture.
+fn filter_lookups(num_faces: i32) -> Vec<ptex::FilterLookup> {
+    let mut lookups = Vec::new();
+    for face_id in 0..num_faces {
+        for i in 0..8 {
+            let u = (i as f32 + 0.5) / 8.0;
+            let v = 1.0 - u;
+            lookups.push(ptex::FilterLookup::new(face_id, u, v, 0.05, 0.05));
+        }
+    }
+    lookups
+}
+
+#[test]
+fn test_filter_eval_batch() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_channels = texture.num_channels();
+    let options = ptex::FilterOptions::new(ptex::FilterType::Gaussian);
+    let mut filter = ptex::Filter::new(&texture, &options)?;
+
+    let lookups = filter_lookups(texture.num_faces());
+    let mut results = vec![0.0_f32; lookups.len() * num_channels as usize];
+    filter.eval_batch(&lookups, 0, num_channels, &mut results)?;
+
+    // Batched results match individual lookups.
+    let mut result = vec![0.0_f32; num_channels as usize];
+    for (lookup, expected) in lookups
+        .iter()
+        .zip(results.chunks_exact(num_channels as usize))
+    {
+        filter.eval(
+            &mut result,
+            0,
+            num_channels,
+            lookup.face_id,
+            lookup.u,
+            lookup.v,
+            lookup.uw1,
+            lookup.vw1,
+            lookup.uw2,
+            lookup.vw2,
+            lookup.width,
+            lookup.blur,
+        )?;
+        for (value, expected) in result.iter().zip(expected) {
+            assert_f32_near!(*value, *expected);
+        }
+    }
+
+    // The results buffer must hold num_channels values per lookup.
+    let mut short = vec![0.0_f32; results.len() - 1];
+    assert!(filter
+        .eval_batch(&lookups, 0, num_channels, &mut short)
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_eval_parallel() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_channels = texture.num_channels();
+    let options = ptex::FilterOptions::new(ptex::FilterType::Bicubic);
+
+    let lookups = filter_lookups(texture.num_faces());
+    let mut expected = vec![0.0_f32; lookups.len() * num_channels as usize];
+    ptex::Filter::new(&texture, &options)?.eval_batch(&lookups, 0, num_channels, &mut expected)?;
+
+    for num_threads in [0, 1, 3, 4, lookups.len() + 1] {
+        let mut results = vec![0.0_f32; expected.len()];
+        ptex::Filter::eval_parallel(
+            &texture,
+            &options,
+            &lookups,
+            0,
+            num_channels,
+            &mut results,
+            num_threads,
+        )?;
+        for (value, expected) in results.iter().zip(&expected) {
+            assert_f32_near!(*value, *expected);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_shared_between_threads() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let expected = texture.pixel_f32(0, 1, 1, 0, 1)?;
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                assert_f32_near!(texture.pixel_f32(0, 1, 1, 0, 1).unwrap(), expected);
+            });
+        }
+    });
+
+    Ok(())
+}
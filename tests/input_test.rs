@@ -0,0 +1,117 @@
+use anyhow::Result;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Input handler that reads from the filesystem and counts the files it opens.
+struct CountingInputHandler {
+    opened: Arc<AtomicUsize>,
+}
+
+impl ptex::InputHandler for CountingInputHandler {
+    fn open(&self, path: &str) -> std::io::Result<Box<dyn ptex::InputStream>> {
+        let file = std::fs::File::open(path)?;
+        self.opened.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(std::io::BufReader::new(file)))
+    }
+}
+
+#[test]
+fn test_cache_from_bytes() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let expected = cache.get(&filename)?;
+
+    let bytes = std::fs::read(&filename)?;
+    let memory_cache = ptex::Cache::from_bytes("memory/test.ptx", bytes, false);
+    let texture = memory_cache.get("memory/test.ptx")?;
+    assert_eq!(texture.num_faces(), expected.num_faces());
+    assert_eq!(texture.num_channels(), expected.num_channels());
+    assert_eq!(texture.data_type(), expected.data_type());
+    for face_id in 0..texture.num_faces() {
+        assert_eq!(
+            texture.face_data::<u16>(face_id)?,
+            expected.face_data::<u16>(face_id)?
+        );
+    }
+
+    // Only the registered path can be opened.
+    assert!(memory_cache.get("tests/fixtures/test.ptx").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_memory_input_handler() -> Result<()> {
+    let bytes: Arc<[u8]> = std::fs::read("tests/fixtures/test.ptx")?.into();
+    let mut input_handler = ptex::MemoryInputHandler::new();
+    input_handler.insert("a.ptx", bytes.clone());
+    input_handler.insert("b.ptx", bytes);
+    assert!(input_handler.contains("a.ptx"));
+    assert!(!input_handler.contains("c.ptx"));
+
+    // Limit the cache to a single open file so that files are reopened.
+    let cache = ptex::Cache::with_input_handler(1, 0, false, input_handler);
+    for _ in 0..2 {
+        for filename in ["a.ptx", "b.ptx"] {
+            let texture = cache.get(filename)?;
+            let data: Vec<u16> = texture.face_data(4)?;
+            assert!(!data.is_empty());
+        }
+    }
+    assert!(cache.get("c.ptx").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_input_handler() -> Result<()> {
+    let opened = Arc::new(AtomicUsize::new(0));
+    let input_handler = CountingInputHandler {
+        opened: opened.clone(),
+    };
+    let cache = ptex::Cache::with_input_handler(0, 0, false, input_handler);
+    let texture = cache.get("tests/fixtures/test.ptx")?;
+    assert_eq!(texture.num_faces(), 9);
+    assert!(opened.load(Ordering::SeqCst) >= 1);
+
+    assert!(matches!(
+        cache.get("tests/fixtures/missing.ptx"),
+        Err(ptex::Error::NotFound(_, _))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_input_handler_open_errors() -> Result<()> {
+    let mut input_handler = ptex::MemoryInputHandler::new();
+    input_handler.insert("invalid.ptx", b"not a ptex file".to_vec());
+    let cache = ptex::Cache::with_input_handler(0, 0, false, input_handler);
+
+    // Missing files are looked up with the input handler, not on the filesystem.
+    assert!(matches!(
+        cache.get("tests/fixtures/test.ptx"),
+        Err(ptex::Error::NotFound(_, _))
+    ));
+    assert!(matches!(
+        cache.get("invalid.ptx"),
+        Err(ptex::Error::InvalidFormat(_, _))
+    ));
+
+    // Files in the search path are also looked up with the input handler.
+    let mut input_handler = ptex::MemoryInputHandler::new();
+    input_handler.insert("textures/invalid.ptx", b"not a ptex file".to_vec());
+    let mut cache = ptex::Cache::with_input_handler(0, 0, false, input_handler);
+    cache.set_search_path("textures");
+    assert!(matches!(
+        cache.get("invalid.ptx"),
+        Err(ptex::Error::InvalidFormat(_, _))
+    ));
+    assert!(matches!(
+        cache.get("missing.ptx"),
+        Err(ptex::Error::NotFound(_, _))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use anyhow::Result;
+
+use ptex::mesh::{barycentric_to_texel, texel_to_barycentric, Diagnostic, MeshAdjacency};
+use ptex::EdgeId;
+
+/// Return the face-vertex counts and indices for a grid of quads.
+///
+/// Faces are numbered in rows and the bottom edge of each face is shared
+/// with the face in the next row, matching the tables in writer_test.rs.
+fn grid(columns: i32, rows: i32) -> (Vec<i32>, Vec<i32>) {
+    let vertex = |x: i32, y: i32| y * (columns + 1) + x;
+    let mut counts = Vec::new();
+    let mut indices = Vec::new();
+    for row in 0..rows {
+        for column in 0..columns {
+            counts.push(4);
+            indices.extend([
+                vertex(column, row + 1),
+                vertex(column + 1, row + 1),
+                vertex(column + 1, row),
+                vertex(column, row),
+            ]);
+        }
+    }
+    (counts, indices)
+}
+
+#[test]
+fn mesh_adjacency_grid() -> Result<()> {
+    let (counts, indices) = grid(3, 3);
+    let mesh = MeshAdjacency::from_quads(&counts, &indices)?;
+    assert_eq!(mesh.num_faces(), 9);
+
+    let adjacent_faces = [
+        [3, 1, -1, -1],
+        [4, 2, -1, 0],
+        [5, -1, -1, 1],
+        [6, 4, 0, -1],
+        [7, 5, 1, 3],
+        [8, -1, 2, 4],
+        [-1, 7, 3, -1],
+        [-1, 8, 4, 6],
+        [-1, -1, 5, 7],
+    ];
+    for (face_id, expected) in adjacent_faces.iter().enumerate() {
+        assert_eq!(mesh.adjacent_faces(face_id), *expected);
+        for (edge, &adjacent_face) in expected.iter().enumerate() {
+            if adjacent_face >= 0 {
+                let expected_edge = [EdgeId::Top, EdgeId::Left, EdgeId::Bottom, EdgeId::Right];
+                assert_eq!(mesh.adjacent_edges(face_id)[edge], expected_edge[edge]);
+            }
+        }
+
+        let face_info = mesh.face_info(face_id, ptex::Res::from_uv(2, 2));
+        assert_eq!(face_info.resolution(), ptex::Res::from_uv(2, 2));
+        for (edge, &adjacent_face) in expected.iter().enumerate() {
+            assert_eq!(face_info.adjacent_face(edge as i32), adjacent_face);
+        }
+    }
+
+    // Every edge on the outside of the grid is a boundary.
+    let boundaries = mesh
+        .diagnostics()
+        .iter()
+        .filter(|diagnostic| matches!(diagnostic, Diagnostic::BoundaryEdge { .. }))
+        .count();
+    assert_eq!(boundaries, 12);
+    assert_eq!(mesh.diagnostics().len(), 12);
+
+    Ok(())
+}
+
+#[test]
+fn mesh_adjacency_diagnostics() -> Result<()> {
+    // Three quads share the edge between vertices 0 and 1.
+    let counts = [4, 4, 4];
+    let indices = [0, 1, 2, 3, 1, 0, 4, 5, 1, 0, 6, 7];
+    let mesh = MeshAdjacency::from_quads(&counts, &indices)?;
+    assert_eq!(mesh.adjacent_faces(0)[0], -1);
+    assert!(mesh.diagnostics().contains(&Diagnostic::NonManifoldEdge {
+        vertices: [0, 1],
+        face_ids: vec![0, 1, 2],
+    }));
+
+    // The second quad is wound in the opposite direction.
+    let counts = [4, 4];
+    let indices = [0, 1, 4, 3, 1, 4, 5, 2];
+    let mesh = MeshAdjacency::from_quads(&counts, &indices)?;
+    assert_eq!(mesh.adjacent_faces(0)[1], 1);
+    assert_eq!(mesh.adjacent_faces(1)[0], 0);
+    assert_eq!(mesh.adjacent_edges(0)[1], EdgeId::Bottom);
+    assert!(mesh
+        .diagnostics()
+        .contains(&Diagnostic::InconsistentOrientation {
+            vertices: [1, 4],
+            face_ids: [0, 1],
+        }));
+
+    // A repeated vertex produces a degenerate edge.
+    let mesh = MeshAdjacency::from_quads(&[4], &[0, 1, 1, 2])?;
+    assert!(mesh.diagnostics().contains(&Diagnostic::DegenerateEdge {
+        face_id: 0,
+        edge: EdgeId::Right,
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn mesh_adjacency_invalid() {
+    assert!(MeshAdjacency::from_quads(&[3], &[0, 1, 2]).is_err());
+    assert!(MeshAdjacency::from_quads(&[4], &[0, 1, 2]).is_err());
+    assert!(MeshAdjacency::from_quads(&[4], &[0, 1, 2, -1]).is_err());
+}
+
+/// Return the face-vertex counts and indices for a consistently-oriented tetrahedron.
+fn tetrahedron() -> (Vec<i32>, Vec<i32>) {
+    (vec![3; 4], vec![0, 1, 2, 0, 3, 1, 1, 3, 2, 0, 2, 3])
+}
+
+#[test]
+fn mesh_adjacency_triangles() -> Result<()> {
+    let (counts, indices) = tetrahedron();
+    let mesh = MeshAdjacency::from_triangles(&counts, &indices)?;
+    assert_eq!(mesh.num_faces(), 4);
+    assert!(mesh.diagnostics().is_empty());
+
+    let expected = [
+        ([1, 2, 3, -1], [EdgeId::Top, EdgeId::Top, EdgeId::Bottom]),
+        ([3, 2, 0, -1], [EdgeId::Top, EdgeId::Bottom, EdgeId::Bottom]),
+        ([1, 3, 0, -1], [EdgeId::Right, EdgeId::Right, EdgeId::Right]),
+        ([0, 2, 1, -1], [EdgeId::Top, EdgeId::Right, EdgeId::Bottom]),
+    ];
+    for (face_id, (adjacent_faces, adjacent_edges)) in expected.iter().enumerate() {
+        assert_eq!(mesh.adjacent_faces(face_id), *adjacent_faces);
+        assert_eq!(mesh.adjacent_edges(face_id)[..3], adjacent_edges[..]);
+    }
+
+    // A single triangle has three boundary edges.
+    let mesh = MeshAdjacency::from_triangles(&[3], &[0, 1, 2])?;
+    assert_eq!(mesh.adjacent_faces(0), [-1; 4]);
+    assert_eq!(mesh.diagnostics().len(), 3);
+
+    assert!(MeshAdjacency::from_triangles(&[4], &[0, 1, 2, 3]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn triangle_texel_layout() {
+    for log2 in 0..4 {
+        let res = ptex::Res::from_uv(log2, log2);
+        let size = res.u();
+        let mut hits = vec![0; (size * size) as usize];
+        for v in 0..size {
+            for u in 0..size {
+                let (bu, bv) = texel_to_barycentric(res, u, v).unwrap();
+                assert!(bu + bv <= 1.0);
+                assert_eq!(barycentric_to_texel(res, bu, bv), (u, v));
+                hits[(v * size + u) as usize] += 1;
+            }
+        }
+        assert!(hits.iter().all(|&count| count == 1));
+        assert_eq!(texel_to_barycentric(res, size, 0), None);
+        assert_eq!(texel_to_barycentric(res, 0, -1), None);
+    }
+
+    let res = ptex::Res::from_uv(1, 1);
+    // Upright sub-triangles are stored in place.
+    assert_eq!(barycentric_to_texel(res, 0.1, 0.1), (0, 0));
+    assert_eq!(barycentric_to_texel(res, 0.6, 0.1), (1, 0));
+    assert_eq!(barycentric_to_texel(res, 0.1, 0.6), (0, 1));
+    // The inverted sub-triangle is stored in the upper-right texel.
+    assert_eq!(barycentric_to_texel(res, 0.4, 0.4), (1, 1));
+    // Corners and coordinates outside of the triangle are clamped.
+    assert_eq!(barycentric_to_texel(res, 1.0, 0.0), (1, 0));
+    assert_eq!(barycentric_to_texel(res, 0.0, 1.0), (0, 1));
+    assert_eq!(barycentric_to_texel(res, 0.5, 0.5), (0, 1));
+    assert_eq!(barycentric_to_texel(res, 2.0, 2.0), (0, 1));
+
+    // The inverted sub-triangle in grid cell (0, 1) is stored with u and v swapped.
+    let res = ptex::Res::from_uv(2, 2);
+    assert_eq!(barycentric_to_texel(res, 0.15, 0.4), (2, 3));
+    let (bu, bv) = texel_to_barycentric(res, 2, 3).unwrap();
+    assert!((bu - 1.0 / 6.0).abs() < 1e-6);
+    assert!((bv - 5.0 / 12.0).abs() < 1e-6);
+}
+
+#[test]
+fn mesh_adjacency_polygons() -> Result<()> {
+    // A quad mesh is not split.
+    let (counts, indices) = grid(3, 3);
+    assert_eq!(
+        MeshAdjacency::from_polygons(&counts, &indices)?,
+        MeshAdjacency::from_quads(&counts, &indices)?
+    );
+
+    // A quad that shares its right edge with a triangle. The triangle is split into
+    // subfaces 1, 2 and 3 at vertices 1, 4 and 2.
+    let counts = [4, 3];
+    let indices = [0, 1, 2, 3, 1, 4, 2];
+    let mesh = MeshAdjacency::from_polygons(&counts, &indices)?;
+    assert_eq!(mesh.num_polygons(), 2);
+    assert_eq!(mesh.num_faces(), 4);
+    assert_eq!(
+        (0..4)
+            .map(|face_id| mesh.is_subface(face_id))
+            .collect::<Vec<_>>(),
+        [false, true, true, true]
+    );
+    assert_eq!(mesh.face_id(0, 3), Some(0));
+    assert_eq!(mesh.face_id(0, 4), None);
+    assert_eq!(mesh.face_id(1, 0), Some(1));
+    assert_eq!(mesh.face_id(1, 2), Some(3));
+    assert_eq!(mesh.face_id(2, 0), None);
+
+    // The quad points at the first subface encountered along its edge.
+    assert_eq!(mesh.adjacent_faces(0), [-1, 1, -1, -1]);
+    assert_eq!(mesh.adjacent_edges(0)[1], EdgeId::Left);
+    assert_eq!(mesh.adjacent_faces(1), [-1, 2, 3, 0]);
+    assert_eq!(
+        mesh.adjacent_edges(1)[1..],
+        [EdgeId::Top, EdgeId::Right, EdgeId::Right]
+    );
+    assert_eq!(mesh.adjacent_faces(2), [-1, 3, 1, -1]);
+    assert_eq!(mesh.adjacent_edges(2)[1..3], [EdgeId::Top, EdgeId::Right]);
+    assert_eq!(mesh.adjacent_faces(3), [0, 1, 2, -1]);
+    assert_eq!(
+        mesh.adjacent_edges(3)[..3],
+        [EdgeId::Right, EdgeId::Top, EdgeId::Right]
+    );
+    assert!(mesh.face_info(1, ptex::Res::from_uv(1, 1)).is_subface());
+    assert!(!mesh.face_info(0, ptex::Res::from_uv(2, 2)).is_subface());
+
+    // Subfaces of two triangles that share an edge are paired half by half.
+    let counts = [3, 3];
+    let indices = [0, 1, 2, 2, 1, 3];
+    let mesh = MeshAdjacency::from_polygons(&counts, &indices)?;
+    assert_eq!(mesh.num_faces(), 6);
+    assert_eq!(mesh.adjacent_faces(1)[0], 4);
+    assert_eq!(mesh.adjacent_edges(1)[0], EdgeId::Left);
+    assert_eq!(mesh.adjacent_faces(2)[3], 3);
+    assert_eq!(mesh.adjacent_edges(2)[3], EdgeId::Bottom);
+    assert_eq!(mesh.adjacent_faces(3)[0], 2);
+    assert_eq!(mesh.adjacent_edges(3)[0], EdgeId::Left);
+    assert_eq!(mesh.adjacent_faces(4)[3], 1);
+    assert_eq!(mesh.adjacent_edges(4)[3], EdgeId::Bottom);
+
+    assert!(MeshAdjacency::from_polygons(&[2], &[0, 1]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn ptex_mesh_validate() {
+    use ptex::mesh::PtexMesh;
+    use ptex::MeshType;
+
+    let positions = vec![0.0; 5 * 3];
+    let mesh = PtexMesh::new(vec![4, 3], vec![0, 1, 4, 3, 1, 2, 4], positions.clone());
+    assert_eq!(mesh.num_vertices(), 5);
+    assert_eq!(mesh.num_ptex_faces(MeshType::Quad), 4);
+    assert_eq!(mesh.num_ptex_faces(MeshType::Triangle), 2);
+    assert!(mesh.validate(MeshType::Quad, 4).is_ok());
+    // The face count must include subfaces.
+    assert!(mesh.validate(MeshType::Quad, 2).is_err());
+    // Triangle meshes may only contain triangles.
+    assert!(mesh.validate(MeshType::Triangle, 2).is_err());
+
+    let mesh = PtexMesh::new(vec![3], vec![0, 1, 2], positions.clone());
+    assert!(mesh.validate(MeshType::Triangle, 1).is_ok());
+    assert_eq!(mesh.adjacency(MeshType::Triangle).unwrap().num_faces(), 1);
+
+    // Vertex indices must refer to vertex positions.
+    let mesh = PtexMesh::new(vec![3], vec![0, 1, 5], positions.clone());
+    assert!(mesh.validate(MeshType::Triangle, 1).is_err());
+    // Positions are xyz triples.
+    let mesh = PtexMesh::new(vec![3], vec![0, 1, 2], vec![0.0; 10]);
+    assert!(mesh.validate(MeshType::Triangle, 1).is_err());
+    // The indices must match the counts.
+    let mesh = PtexMesh::new(vec![3], vec![0, 1], positions);
+    assert!(mesh.validate(MeshType::Triangle, 1).is_err());
+}
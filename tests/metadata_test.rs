@@ -0,0 +1,179 @@
+use anyhow::Result;
+
+use std::fs;
+
+/// Write a single-face texture containing one meta data entry of each type.
+fn write_meta_data_file(filename: &std::path::Path) -> Result<()> {
+    if filename.exists() {
+        fs::remove_file(filename)?;
+    }
+    let writer = ptex::Writer::new(
+        filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::UInt8,
+        1,  // num_channels
+        -1, // alpha_channel
+        1,  // num_faces
+        false,
+    )?;
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(0, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    writer.write_face(0, &face_info, &vec![128_u8], 0)?;
+
+    writer.write_meta_data(c"author", String::from("ptex-bind"))?;
+    writer.write_meta_data(c"int8", vec![-1_i8, 2, -3])?;
+    writer.write_meta_data(c"int16", vec![-300_i16, 300])?;
+    writer.write_meta_data(c"int32", vec![1_i32, 2, 3, 4])?;
+    writer.write_meta_data(c"float", vec![0.5_f32, 1.5])?;
+    writer.write_meta_data(c"double", vec![0.25_f64])?;
+    writer.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_meta_data_round_trip() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/meta_data_round_trip.ptx");
+    write_meta_data_file(&filename)?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let meta_data = texture.meta_data()?;
+        assert_eq!(meta_data.num_keys(), 6);
+
+        let mut keys: Vec<_> = meta_data.keys().map(|(key, _)| key.to_string()).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            ["author", "double", "float", "int16", "int32", "int8"]
+        );
+
+        assert_eq!(
+            meta_data.meta_data_type("author"),
+            Some(ptex::MetaDataType::String)
+        );
+        assert_eq!(
+            meta_data.meta_data_type("int32"),
+            Some(ptex::MetaDataType::Int32)
+        );
+        assert_eq!(meta_data.meta_data_type("missing"), None);
+
+        assert_eq!(meta_data.get_str("author")?, "ptex-bind");
+        assert_eq!(meta_data.get_i8("int8")?, &[-1, 2, -3]);
+        assert_eq!(meta_data.get_i16("int16")?, &[-300, 300]);
+        assert_eq!(meta_data.get_i32("int32")?, &[1, 2, 3, 4]);
+        assert_eq!(meta_data.get_f32("float")?, &[0.5, 1.5]);
+        assert_eq!(meta_data.get_f64("double")?, &[0.25]);
+
+        let map = meta_data.to_map()?;
+        assert_eq!(map.len(), 6);
+        assert_eq!(
+            map.get("author"),
+            Some(&ptex::MetaValue::String("ptex-bind".to_string()))
+        );
+        assert_eq!(
+            map.get("int16"),
+            Some(&ptex::MetaValue::Int16(vec![-300, 300]))
+        );
+        assert_eq!(map["double"].meta_data_type(), ptex::MetaDataType::Double);
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_meta_data_errors() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/meta_data_errors.ptx");
+    write_meta_data_file(&filename)?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let meta_data = texture.meta_data()?;
+
+        assert!(meta_data.get_str("missing").is_err());
+        assert_eq!(
+            meta_data.get_i32("author"),
+            Err(ptex::Error::MetaDataTypeMismatch {
+                key: "author".to_string(),
+                expected: ptex::MetaDataType::Int32,
+                actual: ptex::MetaDataType::String,
+            })
+        );
+        assert!(meta_data.get_f64("float").is_err());
+        assert!(meta_data.get_str("int8").is_err());
+        assert!(meta_data.get_str("bad\0key").is_err());
+        assert!(meta_data.key(-1).is_none());
+        assert!(meta_data.key(meta_data.num_keys()).is_none());
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_mesh_meta_data_round_trip() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/mesh_meta_data_round_trip.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    // A quad next to a triangle, which is split into three subfaces.
+    let mesh = ptex::mesh::PtexMesh::new(
+        vec![4, 3],
+        vec![0, 1, 4, 3, 1, 2, 4],
+        vec![
+            0.0, 0.0, 0.0, //
+            1.0, 0.0, 0.0, //
+            2.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, //
+            1.0, 1.0, 0.0, //
+        ],
+    );
+    let adjacency = mesh.adjacency(ptex::MeshType::Quad)?;
+    assert_eq!(adjacency.num_faces(), 4);
+
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 4).open(&filename)?;
+    for face_id in 0..adjacency.num_faces() {
+        let face_info = adjacency.face_info(face_id, ptex::Res::from_uv(0, 0));
+        writer.write_face(face_id as i32, &face_info, &vec![face_id as u8], 0)?;
+    }
+    mesh.write(&writer)?;
+    writer.close()?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let meta_data = texture.meta_data()?;
+        assert_eq!(
+            meta_data.meta_data_type(ptex::mesh::FACE_VERT_COUNTS_KEY),
+            Some(ptex::MetaDataType::Int32)
+        );
+        assert_eq!(
+            meta_data.meta_data_type(ptex::mesh::FACE_VERT_INDICES_KEY),
+            Some(ptex::MetaDataType::Int32)
+        );
+        assert_eq!(
+            meta_data.meta_data_type(ptex::mesh::VERT_POSITIONS_KEY),
+            Some(ptex::MetaDataType::Float)
+        );
+
+        let read_mesh = ptex::mesh::PtexMesh::from_texture(&texture)?;
+        assert_eq!(read_mesh, mesh);
+        assert_eq!(read_mesh.adjacency(texture.mesh_type())?, adjacency);
+    }
+    fs::remove_file(&filename)?;
+
+    // A texture without mesh meta data.
+    write_meta_data_file(&filename)?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        assert!(ptex::mesh::PtexMesh::from_texture(&texture).is_err());
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
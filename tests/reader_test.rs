@@ -18,7 +18,7 @@ fn test_cache_search_path() -> Result<()> {
 #[test]
 fn test_cache_get() -> Result<()> {
     let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
-    let mut cache = ptex::Cache::new(0, 0, false);
+    let cache = ptex::Cache::new(0, 0, false);
     let texture = cache.get(&filename)?;
     assert!(!texture.is_null());
     assert_eq!(texture.alpha_channel(), -1);
@@ -34,21 +34,104 @@ fn test_cache_get() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cache_stats_and_purge() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    {
+        let texture = cache.get(&filename)?;
+        let data: Vec<u16> = texture.face_data(0)?;
+        assert!(!data.is_empty());
+    }
+
+    let stats = cache.stats();
+    assert!(stats.files_accessed >= 1);
+    assert!(stats.peak_files_open >= 1);
+    assert!(stats.files_open <= stats.peak_files_open);
+    assert!(stats.block_reads > 0);
+    assert!(stats.mem_used > 0);
+    assert!(stats.mem_used <= stats.peak_mem_used);
+
+    // Purged textures are re-read from disk on the next access.
+    cache.purge(&filename);
+    let texture = cache.get(&filename)?;
+    assert_eq!(texture.num_faces(), 9);
+    assert!(cache.stats().files_accessed >= stats.files_accessed);
+
+    cache.purge_all();
+    // Textures that are in use remain valid after a purge.
+    assert_eq!(texture.num_faces(), 9);
+    let stats = cache.stats();
+    assert!(stats.peak_mem_used >= stats.mem_used);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_shared_between_threads() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let expected: Vec<u16> = cache.get(&filename)?.face_data(0)?;
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| cache.get(&filename)?.face_data::<u16>(0)))
+            .collect();
+        for worker in workers {
+            assert_eq!(worker.join().unwrap().unwrap(), expected);
+        }
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_open() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let texture = ptex::Texture::open(&filename, false)?;
+    assert_eq!(texture.num_faces(), 9);
+    assert_eq!(texture.num_channels(), 3);
+    assert_eq!(texture.data_type(), ptex::DataType::UInt16);
+    assert_eq!(filename, texture.filename());
+
+    let cache = ptex::Cache::new(0, 0, false);
+    let cached = cache.get(&filename)?;
+    assert_eq!(texture.face_data::<u16>(4)?, cached.face_data::<u16>(4)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_open_missing() {
+    let result = ptex::Texture::open("tests/fixtures/missing.ptx", false);
+    match result {
+        Err(ptex::Error::NotFound(path, message)) => {
+            assert!(path.ends_with("missing.ptx"));
+            assert!(!message.is_empty());
+        }
+        _ => panic!("expected an error for a missing file"),
+    }
+
+    // Files that exist but are not Ptex files have an invalid format.
+    let result = ptex::Texture::open("tests/reader_test.rs", false);
+    assert!(matches!(result, Err(ptex::Error::InvalidFormat(_, _))));
+}
+
 #[test]
 fn test_face_info() -> Result<()> {
     let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
-    let mut cache = ptex::Cache::new(0, 0, false);
+    let cache = ptex::Cache::new(0, 0, false);
     let texture = cache.get(&filename)?;
     assert_eq!(texture.num_faces(), 9);
 
-    let face_info = texture.face_info(0);
+    let face_info = texture.face_info(0)?;
     assert!(!face_info.has_edits());
     assert!(!face_info.is_constant());
     assert!(!face_info.is_neighborhood_constant());
     assert!(!face_info.is_subface());
     assert_eq!(face_info.adjacent_edge(0), ptex::EdgeId::Top);
 
-    let face_info = texture.face_info(0);
+    let face_info = texture.face_info(0)?;
     assert_eq!(face_info.adjacent_face(0), 3);
 
     Ok(())
@@ -57,24 +140,24 @@ fn test_face_info() -> Result<()> {
 #[test]
 fn test_face_info_set_adjfaces() -> Result<()> {
     let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
-    let mut cache = ptex::Cache::new(0, 0, false);
+    let cache = ptex::Cache::new(0, 0, false);
     let texture = cache.get(&filename)?;
     assert_eq!(texture.num_faces(), 9);
 
-    let face_info = texture.face_info(0);
+    let face_info = texture.face_info(0)?;
     assert_eq!(face_info.adjacent_face(0), 3);
     assert_eq!(face_info.adjacent_face(1), 1);
     assert_eq!(face_info.adjacent_face(2), -1);
     assert_eq!(face_info.adjacent_face(3), -1);
 
-    let mut face_info = texture.face_info(0);
+    let mut face_info = texture.face_info(0)?;
     face_info.set_adjacent_faces(1, 2, 3, 4);
     assert_eq!(face_info.adjacent_face(0), 1);
     assert_eq!(face_info.adjacent_face(1), 2);
     assert_eq!(face_info.adjacent_face(2), 3);
     assert_eq!(face_info.adjacent_face(3), 4);
 
-    let mut face_info = texture.face_info(1);
+    let mut face_info = texture.face_info(1)?;
     assert_eq!(face_info.adjacent_edge(0), ptex::EdgeId::Top);
     assert_eq!(face_info.adjacent_edge(1), ptex::EdgeId::Left);
     assert_eq!(face_info.adjacent_edge(2), ptex::EdgeId::Bottom);
@@ -97,44 +180,94 @@ fn test_face_info_set_adjfaces() -> Result<()> {
 #[test]
 fn test_texture_pixel() -> Result<()> {
     let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
-    let mut cache = ptex::Cache::new(0, 0, false);
+    let cache = ptex::Cache::new(0, 0, false);
     let texture = cache.get(&filename)?;
 
     assert!(texture.num_faces() > 1);
-    let face_info = texture.face_info(0);
+    let face_info = texture.face_info(0)?;
     let res = face_info.resolution();
     assert_eq!(res.u(), 256);
     assert_eq!(res.v(), 128);
 
-    let pixel = texture.pixel_f32(0, 0, 1, 0, 1);
+    let pixel = texture.pixel_f32(0, 0, 1, 0, 1)?;
     assert_eq!(pixel, 0.0);
 
-    let pixel = texture.pixel_f32(0, 0, 1, 1, 1);
+    let pixel = texture.pixel_f32(0, 0, 1, 1, 1)?;
     assert_f32_near!(pixel, 0.007873655);
 
-    let pixel = texture.pixel_f32(0, 0, 1, 2, 1);
+    let pixel = texture.pixel_f32(0, 0, 1, 2, 1)?;
     assert_eq!(pixel, 1.0);
 
-    let pixel = texture.pixel_f32(1, 1, 1, 1, 1);
+    let pixel = texture.pixel_f32(1, 1, 1, 1, 1)?;
     assert_f32_near!(pixel, 0.33333334);
 
     Ok(())
 }
 
+#[test]
+fn test_texture_bounds_checks() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_faces = texture.num_faces();
+
+    assert_eq!(
+        texture.face_info(num_faces).err(),
+        Some(ptex::Error::FaceIdOutOfRange {
+            face_id: num_faces,
+            num_faces,
+        })
+    );
+    assert!(texture.face_info(-1).is_err());
+    let res = unsafe { texture.face_info_unchecked(0) }.resolution();
+    assert_eq!(res, texture.face_info(0)?.resolution());
+
+    assert!(texture.pixel_f32(-1, 0, 0, 0, 1).is_err());
+    assert_eq!(
+        texture.pixel_f32(0, res.u(), 0, 0, 1),
+        Err(ptex::Error::TexelOutOfRange {
+            face_id: 0,
+            u: res.u(),
+            v: 0,
+            ures: res.u(),
+            vres: res.v(),
+        })
+    );
+    assert!(texture.pixel_f32(0, 0, res.v(), 0, 1).is_err());
+    assert!(texture.pixel_f32(0, 0, -1, 0, 1).is_err());
+    assert_eq!(
+        texture.pixel_f32(0, 0, 0, 2, 2),
+        Err(ptex::Error::ChannelOutOfRange {
+            first_channel: 2,
+            num_channels: 2,
+            available: 3,
+        })
+    );
+    assert!(texture.pixel_f32(0, 0, 0, -1, 1).is_err());
+    assert!(texture.pixel_f32(0, 0, 0, 0, 0).is_err());
+
+    // Multiple channels may be accessed; the first channel's value is returned.
+    let pixel = texture.pixel_f32(0, 0, 1, 0, 3)?;
+    let unchecked = unsafe { texture.pixel_f32_unchecked(0, 0, 1, 0, 1) };
+    assert_eq!(pixel, unchecked);
+
+    Ok(())
+}
+
 #[test]
 fn test_faceinfo_set_resolution() -> Result<()> {
     let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
-    let mut cache = ptex::Cache::new(0, 0, false);
+    let cache = ptex::Cache::new(0, 0, false);
     let texture = cache.get(&filename)?;
 
     let base = 2_i32;
-    let face_info = texture.face_info(0);
+    let face_info = texture.face_info(0)?;
     let res = face_info.resolution();
     assert_eq!(res.u(), base.pow(8));
     assert_eq!(res.v(), base.pow(7));
 
     let res = ptex::Res::from_uv(3, 4);
-    let mut face_info = texture.face_info(0);
+    let mut face_info = texture.face_info(0)?;
     face_info.set_resolution(res);
 
     let res = face_info.resolution();
@@ -144,3 +277,270 @@ fn test_faceinfo_set_resolution() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_texture_face_data() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_channels = texture.num_channels() as usize;
+    let one_value = ptex::OneValue::get(texture.data_type());
+
+    let res = texture.face_info(0)?.resolution();
+    let data: Vec<u16> = texture.face_data(0)?;
+    assert_eq!(data.len(), res.size() * num_channels);
+
+    for (u, v) in [(0, 0), (0, 1), (5, 3), (255, 127)] {
+        for channel in 0..num_channels {
+            let idx = ((v * res.u() + u) as usize * num_channels) + channel;
+            let pixel = texture.pixel_f32(0, u, v, channel as i32, 1)?;
+            assert_f32_near!(data[idx] as f32 / one_value, pixel);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_read_face_into_stride() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    let num_channels = texture.num_channels() as usize;
+
+    let res = texture.face_info(1)?.resolution();
+    let packed: Vec<u16> = texture.face_data(1)?;
+
+    // Pad each row with one extra texel.
+    let row_len = res.u() as usize * num_channels;
+    let padded_row_len = row_len + num_channels;
+    let stride = (padded_row_len * std::mem::size_of::<u16>()) as i32;
+    let mut padded = vec![0_u16; padded_row_len * res.v() as usize];
+    texture.read_face_into(1, &mut padded, stride)?;
+
+    for v in 0..res.v() as usize {
+        assert_eq!(
+            &packed[v * row_len..(v + 1) * row_len],
+            &padded[v * padded_row_len..v * padded_row_len + row_len]
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_face_data_errors() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+
+    // The fixture stores u16 data.
+    assert_eq!(
+        texture.face_data::<f32>(0),
+        Err(ptex::Error::DataTypeMismatch {
+            expected: ptex::DataType::UInt16,
+            actual: ptex::DataType::Float32,
+        })
+    );
+    assert_eq!(
+        texture.face_data::<u16>(-1),
+        Err(ptex::Error::FaceIdOutOfRange {
+            face_id: -1,
+            num_faces: 9,
+        })
+    );
+    assert!(texture.face_data::<u16>(texture.num_faces()).is_err());
+
+    let mut small = vec![0_u16; 4];
+    assert_eq!(
+        texture.read_face_into(0, &mut small, 0),
+        Err(ptex::Error::BufferTooSmall {
+            required: 256 * 128 * 3,
+            actual: 4,
+        })
+    );
+
+    let res = texture.face_info(0)?.resolution();
+    let mut buf = vec![0_u16; res.size() * texture.num_channels() as usize];
+    assert!(matches!(
+        texture.read_face_into(0, &mut buf, 2),
+        Err(ptex::Error::InvalidStride { stride: 2, .. })
+    ));
+    assert!(matches!(
+        texture.read_face_into(0, &mut buf, -1),
+        Err(ptex::Error::InvalidStride { stride: -1, .. })
+    ));
+    assert!(texture.read_face_into(0, &mut buf, 0).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_face_data_at_res() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/fixtures/test.ptx");
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    assert!(texture.has_mip_maps());
+    let num_channels = texture.num_channels() as usize;
+
+    // Reading at the stored resolution is equivalent to face_data().
+    let res = texture.face_info(0)?.resolution();
+    let full: Vec<u16> = texture.face_data_at_res(0, res)?;
+    assert_eq!(full, texture.face_data::<u16>(0)?);
+
+    let reduced_res = ptex::Res::from_uv(7, 6);
+    let reduced: Vec<u16> = texture.face_data_at_res(0, reduced_res)?;
+    assert_eq!(reduced.len(), 128 * 64 * num_channels);
+
+    let tiny: Vec<u16> = texture.face_data_at_res(0, ptex::Res::from_uv(0, 0))?;
+    assert_eq!(tiny.len(), num_channels);
+
+    // Resolutions larger than the stored resolution are rejected.
+    assert_eq!(
+        texture.face_data_at_res::<u16>(0, ptex::Res::from_uv(9, 7)),
+        Err(ptex::Error::InvalidResolution {
+            face_id: 0,
+            res: ptex::Res::from_uv(9, 7),
+            face_res: res,
+        })
+    );
+    assert!(texture
+        .face_data_at_res::<u16>(0, ptex::Res::from_uv(8, 8))
+        .is_err());
+    assert!(texture
+        .face_data_at_res::<u16>(0, ptex::Res::from_uv(-1, 0))
+        .is_err());
+
+    Ok(())
+}
+
+/// Write two adjacent quads. The second quad is rotated so that the right edge of
+/// face 0 is shared with the bottom edge of face 1.
+fn write_rotated_quads(
+    filename: &std::path::Path,
+    border_mode_u: ptex::BorderMode,
+    border_mode_v: ptex::BorderMode,
+) -> Result<()> {
+    if filename.exists() {
+        std::fs::remove_file(filename)?;
+    }
+    let mesh = ptex::mesh::MeshAdjacency::from_quads(&[4, 4], &[0, 1, 4, 3, 4, 1, 2, 5])?;
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2)
+        .border_modes(border_mode_u, border_mode_v)
+        .open(filename)?;
+    let face_res = [ptex::Res::from_uv(1, 1), ptex::Res::from_uv(2, 2)];
+    for (face_id, res) in face_res.into_iter().enumerate() {
+        // Use varying texels so that Ptex does not store constant faces.
+        let buf: Vec<u8> = (0..res.size() as u8).map(|i| i + 1).collect();
+        writer.write_face(face_id as i32, &mesh.face_info(face_id, res), &buf, 0)?;
+    }
+    writer.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_resolve_texel() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/texture_resolve_texel.ptx");
+    write_rotated_quads(
+        &filename,
+        ptex::BorderMode::Clamp,
+        ptex::BorderMode::Periodic,
+    )?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let texel = |face_id, u, v| Some(ptex::TexelLocation { face_id, u, v });
+
+        // Texels inside the face are unchanged.
+        assert_eq!(texture.resolve_texel(0, 1, 1)?, texel(0, 1, 1));
+
+        // Face 0 is 2x2 and face 1 is 4x4. Texels past the right edge of face 0 are
+        // rotated into face 1 and scaled to its resolution.
+        assert_eq!(texture.resolve_texel(0, 2, 0)?, texel(1, 3, 1));
+        assert_eq!(texture.resolve_texel(0, 2, 1)?, texel(1, 1, 1));
+        assert_eq!(texture.resolve_texel(1, 3, -1)?, texel(0, 1, 0));
+        assert_eq!(texture.resolve_texel(1, 0, -1)?, texel(0, 1, 1));
+
+        // Mesh boundaries are clamped in u and wrapped in v.
+        assert_eq!(texture.resolve_texel(0, -1, 1)?, texel(0, 0, 1));
+        assert_eq!(texture.resolve_texel(0, -3, 0)?, texel(0, 0, 0));
+        assert_eq!(texture.resolve_texel(0, 0, -1)?, texel(0, 0, 1));
+        assert_eq!(texture.resolve_texel(0, 1, 2)?, texel(0, 1, 0));
+
+        let pixel = texture.pixel_f32_across_edges(0, 2, 0, 0, 1)?;
+        assert_eq!(pixel, texture.pixel_f32(1, 3, 1, 0, 1)?);
+        assert_f32_near!(pixel, 8.0 / 255.0);
+
+        assert!(texture.resolve_texel(2, 0, 0).is_err());
+        assert!(texture.pixel_f32_across_edges(0, 2, 0, 1, 1).is_err());
+    }
+
+    write_rotated_quads(&filename, ptex::BorderMode::Black, ptex::BorderMode::Black)?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        assert_eq!(texture.resolve_texel(0, -1, 0)?, None);
+        assert_eq!(texture.resolve_texel(1, 4, 0)?, None);
+        assert_eq!(texture.pixel_f32_across_edges(0, 0, -1, 0, 1)?, 0.0);
+        assert!(texture.pixel_f32_across_edges(0, 0, -1, 0, 2).is_err());
+    }
+    std::fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_texture_resolve_texel_subfaces() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/texture_resolve_texel_subfaces.ptx");
+    if filename.exists() {
+        std::fs::remove_file(&filename)?;
+    }
+    // A quad next to a pentagon, which is split into five subfaces. The right edge of
+    // the quad is shared with subface 1 (near vertex 1) and subface 5 (near vertex 2).
+    let mesh = ptex::mesh::MeshAdjacency::from_polygons(&[4, 5], &[0, 1, 2, 3, 1, 4, 5, 6, 2])?;
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, mesh.num_faces() as i32)
+        .open(&filename)?;
+    for face_id in 0..mesh.num_faces() {
+        // Subfaces have half the resolution of regular faces.
+        let res = if mesh.is_subface(face_id) {
+            ptex::Res::from_uv(1, 1)
+        } else {
+            ptex::Res::from_uv(2, 2)
+        };
+        let buf: Vec<u8> = (0..res.size() as u8).map(|i| i + 1).collect();
+        writer.write_face(face_id as i32, &mesh.face_info(face_id, res), &buf, 0)?;
+    }
+    writer.close()?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let texel = |face_id, u, v| Some(ptex::TexelLocation { face_id, u, v });
+
+        // The first half of the quad's edge crosses into subface 1 and the second half
+        // into subface 5. Subfaces cover half of the quad, so distances are doubled.
+        assert_eq!(texture.resolve_texel(0, 4, 0)?, texel(1, 0, 0));
+        assert_eq!(texture.resolve_texel(0, 4, 1)?, texel(1, 0, 1));
+        assert_eq!(texture.resolve_texel(0, 4, 2)?, texel(5, 1, 0));
+        assert_eq!(texture.resolve_texel(0, 4, 3)?, texel(5, 0, 0));
+        assert_eq!(texture.resolve_texel(0, 5, 0)?, texel(1, 1, 0));
+        assert_eq!(texture.resolve_texel(0, 5, 3)?, texel(5, 0, 1));
+
+        // Crossing back from either subface lands on its half of the quad's edge, and
+        // distances are halved.
+        assert_eq!(texture.resolve_texel(1, -1, 0)?, texel(0, 3, 0));
+        assert_eq!(texture.resolve_texel(1, -1, 1)?, texel(0, 3, 1));
+        assert_eq!(texture.resolve_texel(5, 0, -1)?, texel(0, 3, 3));
+        assert_eq!(texture.resolve_texel(5, 1, -1)?, texel(0, 3, 2));
+        assert_eq!(texture.resolve_texel(1, -2, 0)?, texel(0, 2, 0));
+        assert_eq!(texture.resolve_texel(5, 1, -2)?, texel(0, 2, 2));
+
+        assert_eq!(
+            texture.pixel_f32_across_edges(0, 4, 3, 0, 1)?,
+            texture.pixel_f32(5, 0, 0, 0, 1)?
+        );
+    }
+    std::fs::remove_file(&filename)?;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+
+use ptex::{AdjacencyReport, AdjacencyViolation, EdgeId};
+use std::fs;
+
+/// Write a single-channel quad texture with the specified adjacency for each face.
+fn write_faces(filename: &std::path::Path, faces: &[([i32; 4], [i32; 4], bool)]) -> Result<()> {
+    if filename.exists() {
+        fs::remove_file(filename)?;
+    }
+    let writer =
+        ptex::WriterOptions::new(ptex::DataType::UInt8, 1, faces.len() as i32).open(filename)?;
+    for (face_id, (adjacent_faces, adjacent_edges, is_subface)) in faces.iter().enumerate() {
+        let face_info = ptex::FaceInfo::from_res_and_adjacency(
+            ptex::Res::from_uv(1, 1),
+            adjacent_faces,
+            adjacent_edges,
+            *is_subface,
+        );
+        // Use varying texels so that Ptex does not store constant faces.
+        writer.write_face(face_id as i32, &face_info, &vec![0_u8, 1, 2, 3], 0)?;
+    }
+    writer.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_mesh_adjacency() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/validate_mesh_adjacency.ptx");
+    // A quad next to a pentagon, which is split into five subfaces.
+    let mesh = ptex::mesh::MeshAdjacency::from_polygons(&[4, 5], &[0, 1, 2, 3, 1, 4, 5, 6, 2])?;
+    let faces: Vec<_> = (0..mesh.num_faces())
+        .map(|face_id| {
+            (
+                mesh.adjacent_faces(face_id),
+                mesh.adjacent_edges(face_id).map(|edge| edge as i32),
+                mesh.is_subface(face_id),
+            )
+        })
+        .collect();
+    write_faces(&filename, &faces)?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let report = AdjacencyReport::from_texture(&texture)?;
+        assert_eq!(report.num_faces(), 6);
+        assert_eq!(report.violations(), &[]);
+        assert!(report.is_valid());
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_violations() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/validate_violations.ptx");
+    write_faces(
+        &filename,
+        &[
+            // Face 1 does not point back to face 0.
+            ([-1, 1, -1, -1], [0, 3, 0, 0], false),
+            ([-1, -1, -1, -1], [0, 0, 0, 0], false),
+            // An out of range face id, and a subface whose interior edge borders face 0.
+            ([7, 0, -1, -1], [0, 3, 0, 0], true),
+        ],
+    )?;
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        let report = AdjacencyReport::from_texture(&texture)?;
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.violations(),
+            &[
+                AdjacencyViolation::NotReciprocal {
+                    face_id: 0,
+                    edge: EdgeId::Right,
+                    adjacent_face: 1,
+                    adjacent_edge: EdgeId::Left,
+                    actual_face: -1,
+                    actual_edge: EdgeId::Bottom,
+                },
+                AdjacencyViolation::FaceIdOutOfRange {
+                    face_id: 2,
+                    edge: EdgeId::Bottom,
+                    adjacent_face: 7,
+                },
+                AdjacencyViolation::SubfaceMismatch {
+                    face_id: 2,
+                    edge: EdgeId::Right,
+                    adjacent_face: 0,
+                },
+                AdjacencyViolation::NotReciprocal {
+                    face_id: 2,
+                    edge: EdgeId::Right,
+                    adjacent_face: 0,
+                    adjacent_edge: EdgeId::Left,
+                    actual_face: -1,
+                    actual_edge: EdgeId::Bottom,
+                },
+            ]
+        );
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
@@ -73,7 +73,7 @@ fn ptex_writer_u8() -> Result<()> {
         fs::remove_file(&filename)?;
     }
 
-    let mut ptex_writer = ptex::Writer::new(
+    let ptex_writer = ptex::Writer::new(
         &filename,
         mesh_type,
         data_type,
@@ -115,7 +115,7 @@ fn ptex_writer_u8() -> Result<()> {
             false,
         );
 
-        assert!(ptex_writer.write_face(i as i32, &face_info, &buf, stride));
+        ptex_writer.write_face(i as i32, &face_info, &buf, stride)?;
     }
 
     assert_eq!(ptex_writer.close(), Ok(()));
@@ -141,7 +141,7 @@ fn ptex_writer_u16() -> Result<()> {
         fs::remove_file(&filename)?;
     }
 
-    let mut ptex_writer = ptex::Writer::new(
+    let ptex_writer = ptex::Writer::new(
         &filename,
         mesh_type,
         data_type,
@@ -183,7 +183,7 @@ fn ptex_writer_u16() -> Result<()> {
             false,
         );
 
-        assert!(ptex_writer.write_face(i as i32, &face_info, &buf, stride));
+        ptex_writer.write_face(i as i32, &face_info, &buf, stride)?;
     }
 
     assert_eq!(ptex_writer.close(), Ok(()));
@@ -209,7 +209,7 @@ fn ptex_writer_f16() -> Result<()> {
         fs::remove_file(&filename)?;
     }
 
-    let mut ptex_writer = ptex::Writer::new(
+    let ptex_writer = ptex::Writer::new(
         &filename,
         mesh_type,
         data_type,
@@ -249,7 +249,7 @@ fn ptex_writer_f16() -> Result<()> {
             false,
         );
 
-        assert!(ptex_writer.write_face(i as i32, &face_info, &buf, stride));
+        ptex_writer.write_face(i as i32, &face_info, &buf, stride)?;
     }
 
     assert_eq!(ptex_writer.close(), Ok(()));
@@ -275,7 +275,7 @@ fn ptex_writer_f32() -> Result<()> {
         fs::remove_file(&filename)?;
     }
 
-    let mut ptex_writer = ptex::Writer::new(
+    let ptex_writer = ptex::Writer::new(
         &filename,
         mesh_type,
         data_type,
@@ -316,7 +316,7 @@ fn ptex_writer_f32() -> Result<()> {
             false,
         );
 
-        assert!(ptex_writer.write_face(i as i32, &face_info, &buf, stride));
+        ptex_writer.write_face(i as i32, &face_info, &buf, stride)?;
     }
 
     assert_eq!(ptex_writer.close(), Ok(()));
@@ -325,3 +325,726 @@ fn ptex_writer_f32() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn ptex_writer_errors() -> Result<()> {
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(0, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+
+    // Ptex may report an unwritable destination when opening or when closing.
+    let filename = std::path::PathBuf::from("tests/tmp/missing-directory/ptex_writer_errors.ptx");
+    let result = ptex::Writer::new(
+        &filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::UInt8,
+        1,
+        -1,
+        1,
+        false,
+    )
+    .and_then(|writer| {
+        writer.write_face(0, &face_info, &vec![0_u8], 0)?;
+        writer.close()
+    });
+    match result {
+        Err(ptex::Error::WriteFailed(path, _)) => assert_eq!(path, filename),
+        _ => panic!("expected a write error for a missing directory"),
+    }
+
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_errors.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let writer = ptex::Writer::new(
+        &filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::UInt8,
+        1,
+        -1,
+        1,
+        false,
+    )?;
+    assert_eq!(
+        writer.write_face(1, &face_info, &vec![0_u8], 0),
+        Err(ptex::Error::FaceIdOutOfRange {
+            face_id: 1,
+            num_faces: 1,
+        })
+    );
+    writer.write_face(0, &face_info, &vec![0_u8], 0)?;
+    writer.close()?;
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_buffer_checks() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_buffer_checks.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let num_channels = 2;
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(2, 2),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    let writer = ptex::Writer::new(
+        &filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::UInt8,
+        num_channels,
+        -1,
+        1,
+        false,
+    )?;
+
+    // 4x4 texels with 2 channels each.
+    let size = 16 * num_channels as usize;
+    assert_eq!(
+        writer.write_face(0, &face_info, &vec![0_u16; size], 0),
+        Err(ptex::Error::DataTypeMismatch {
+            expected: ptex::DataType::UInt8,
+            actual: ptex::DataType::UInt16,
+        })
+    );
+    assert_eq!(
+        writer.write_face(0, &face_info, &vec![0_u8; size - 1], 0),
+        Err(ptex::Error::BufferTooSmall {
+            required: size,
+            actual: size - 1,
+        })
+    );
+    // Rows of 8 values with a stride of 10 bytes end at 3 * 10 + 8.
+    assert_eq!(
+        writer.write_face(0, &face_info, &vec![0_u8; 37], 10),
+        Err(ptex::Error::BufferTooSmall {
+            required: 38,
+            actual: 37,
+        })
+    );
+    // The stride must be able to hold a row.
+    assert_eq!(
+        writer.write_face(0, &face_info, &vec![0_u8; size], 4),
+        Err(ptex::Error::InvalidStride {
+            stride: 4,
+            row_size: 8,
+            value_size: 1,
+        })
+    );
+
+    writer.write_face(0, &face_info, &vec![0_u8; 38], 10)?;
+    writer.close()?;
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+/// Write a two-face, single-channel u8 texture filled with the specified values.
+fn write_two_face_file(filename: &std::path::Path, values: [u8; 2]) -> Result<()> {
+    if filename.exists() {
+        fs::remove_file(filename)?;
+    }
+    let writer = ptex::Writer::new(
+        filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::UInt8,
+        1,
+        -1,
+        2,
+        false,
+    )?;
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(2, 2),
+        &[-1, 1, -1, -1],
+        &[0, 3, 0, 0],
+        false,
+    );
+    writer.write_face(0, &face_info, &vec![values[0]; 16], 0)?;
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(2, 2),
+        &[-1, -1, -1, 0],
+        &[0, 0, 0, 1],
+        false,
+    );
+    writer.write_face(1, &face_info, &vec![values[1]; 16], 0)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_edit() -> Result<()> {
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(2, 2),
+        &[-1, -1, -1, 0],
+        &[0, 0, 0, 1],
+        false,
+    );
+
+    for incremental in [false, true] {
+        let filename =
+            std::path::PathBuf::from(format!("tests/tmp/ptex_writer_edit_{incremental}.ptx"));
+        write_two_face_file(&filename, [10, 20])?;
+
+        // Replace face 1 and leave face 0 untouched.
+        let writer = ptex::Writer::edit(
+            &filename,
+            incremental,
+            ptex::MeshType::Quad,
+            ptex::DataType::UInt8,
+            1,
+            -1,
+            2,
+            false,
+        )?;
+        writer.write_face(1, &face_info, &vec![30_u8; 16], 0)?;
+        writer.close()?;
+
+        {
+            let texture = ptex::Texture::open(&filename, false)?;
+            assert_eq!(texture.has_edits(), incremental);
+            assert_eq!(texture.face_data::<u8>(0)?, vec![10_u8; 16]);
+            assert_eq!(texture.face_data::<u8>(1)?, vec![30_u8; 16]);
+        }
+        fs::remove_file(&filename)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_edit_header_mismatch() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_edit_header_mismatch.ptx");
+    write_two_face_file(&filename, [10, 20])?;
+
+    let result = ptex::Writer::edit(
+        &filename,
+        true,
+        ptex::MeshType::Quad,
+        ptex::DataType::Float32,
+        1,
+        -1,
+        2,
+        false,
+    );
+    match result {
+        Err(ptex::Error::WriteFailed(path, _)) => assert_eq!(path, filename),
+        _ => panic!("expected a write error for a mismatched header"),
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_constant_faces() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_constant_faces.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let res = ptex::Res::from_uv(2, 2);
+    let face_infos = [
+        ptex::FaceInfo::from_res_and_adjacency(res, &[-1, 1, -1, -1], &[0, 3, 0, 0], false),
+        ptex::FaceInfo::from_res_and_adjacency(res, &[-1, 2, -1, 0], &[0, 3, 0, 1], false),
+        ptex::FaceInfo::from_res_and_adjacency(res, &[-1, -1, -1, 1], &[0, 0, 0, 1], false),
+    ];
+    let mut writer = ptex::Writer::new(
+        &filename,
+        ptex::MeshType::Quad,
+        ptex::DataType::Float32,
+        3,
+        -1,
+        3,
+        false,
+    )?;
+    assert!(!writer.detect_constant_faces());
+
+    // Constant faces take a single texel.
+    writer.write_constant_face(0, &face_infos[0], &[0.25_f32, 0.5, 0.75])?;
+    assert_eq!(
+        writer.write_constant_face(0, &face_infos[0], &[0_u8, 0, 0]),
+        Err(ptex::Error::DataTypeMismatch {
+            expected: ptex::DataType::Float32,
+            actual: ptex::DataType::UInt8,
+        })
+    );
+    assert_eq!(
+        writer.write_constant_face(0, &face_infos[0], &[0.0_f32, 0.0]),
+        Err(ptex::Error::BufferTooSmall {
+            required: 3,
+            actual: 2,
+        })
+    );
+
+    // Uniform buffers are detected when enabled.
+    writer.set_detect_constant_faces(true);
+    let uniform: Vec<f32> = [1.0_f32, 0.0, 0.5].repeat(res.size());
+    writer.write_face(1, &face_infos[1], &uniform, 0)?;
+    let mut varying = uniform.clone();
+    varying[4] = 0.25;
+    writer.write_face(2, &face_infos[2], &varying, 0)?;
+    writer.close()?;
+
+    {
+        let texture = ptex::Texture::open(&filename, false)?;
+        assert!(texture.face_info(0)?.is_constant());
+        assert!(texture.face_info(1)?.is_constant());
+        assert!(!texture.face_info(2)?.is_constant());
+        assert_eq!(texture.face_info(0)?.resolution(), res);
+        assert_eq!(
+            texture.face_data::<f32>(0)?,
+            [0.25_f32, 0.5, 0.75].repeat(res.size())
+        );
+        assert_eq!(texture.face_data::<f32>(1)?, uniform);
+        assert_eq!(texture.face_data::<f32>(2)?, varying);
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_options() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_options.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(1, 1),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    let options = ptex::WriterOptions::new(ptex::DataType::UInt16, 2, 1)
+        .alpha_channel(1)
+        .generate_mipmaps(true)
+        .border_modes(ptex::BorderMode::Periodic, ptex::BorderMode::Black)
+        .edge_filter_mode(ptex::EdgeFilterMode::TangentVector);
+
+    let writer = options.open(&filename)?;
+    writer.write_face(0, &face_info, &vec![1_u16; 8], 0)?;
+    writer.close()?;
+
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    assert_eq!(texture.mesh_type(), ptex::MeshType::Quad);
+    assert_eq!(texture.data_type(), ptex::DataType::UInt16);
+    assert_eq!(texture.num_channels(), 2);
+    assert_eq!(texture.alpha_channel(), 1);
+    assert_eq!(texture.num_faces(), 1);
+    assert!(texture.has_mip_maps());
+    assert_eq!(texture.border_mode_u(), ptex::BorderMode::Periodic);
+    assert_eq!(texture.border_mode_v(), ptex::BorderMode::Black);
+    assert_eq!(
+        texture.edge_filter_mode(),
+        ptex::EdgeFilterMode::TangentVector
+    );
+    drop(texture);
+    cache.purge(&filename);
+
+    // Header options that do not match the existing file are rejected when editing.
+    let result = options
+        .data_type(ptex::DataType::Float32)
+        .edit(&filename, true);
+    assert!(matches!(result, Err(ptex::Error::WriteFailed(_, _))));
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_drop_without_close() -> Result<()> {
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(0, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+
+    // An unclosed new file is removed when the writer is dropped.
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_drop_without_close.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2).open(&filename)?;
+    writer.write_face(0, &face_info, &vec![1_u8], 0)?;
+    drop(writer);
+    assert!(!filename.exists());
+
+    // Closing leaves a finished file behind.
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 1).open(&filename)?;
+    writer.write_face(0, &face_info, &vec![1_u8], 0)?;
+    writer.close()?;
+    assert!(filename.exists());
+    fs::remove_file(&filename)?;
+
+    // A file that existed before the writer was opened is not deleted.
+    write_two_face_file(&filename, [10, 20])?;
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2).open(&filename)?;
+    writer.write_face(0, &face_info, &vec![30_u8], 0)?;
+    drop(writer);
+    assert!(filename.exists());
+
+    // Ptex rejects non-square triangle faces and fails to close the file, leaving the
+    // existing file untouched.
+    write_two_face_file(&filename, [10, 20])?;
+    let original = fs::read(&filename)?;
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2)
+        .mesh_type(ptex::MeshType::Triangle)
+        .open(&filename)?;
+    let non_square = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(1, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    assert!(writer
+        .write_face(0, &non_square, &vec![1_u8, 2], 0)
+        .is_err());
+    assert!(matches!(
+        writer.close(),
+        Err(ptex::Error::WriteFailed(_, _))
+    ));
+    assert_eq!(fs::read(&filename)?, original);
+    assert!(!std::path::Path::new("tests/tmp/ptex_writer_drop_without_close.ptx.new").exists());
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+/// Return the names of files in tests/tmp that start with a prefix.
+fn tmp_files_with_prefix(prefix: &str) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir("tests/tmp")? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if name.starts_with(prefix) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+#[test]
+fn ptex_writer_atomic() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_atomic.ptx");
+    let temp_prefix = ".ptex_writer_atomic.ptx.";
+    write_two_face_file(&filename, [10, 20])?;
+    let original = fs::read(&filename)?;
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(0, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    let options = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2)
+        .atomic(true)
+        .sync(true);
+
+    // The destination is untouched until the writer is closed.
+    let writer = options.open(&filename)?;
+    writer.write_face(0, &face_info, &vec![30_u8], 0)?;
+    writer.write_face(1, &face_info, &vec![40_u8], 0)?;
+    assert_eq!(fs::read(&filename)?, original);
+
+    // Dropping the writer removes the temporary file.
+    drop(writer);
+    assert_eq!(fs::read(&filename)?, original);
+    assert!(tmp_files_with_prefix(temp_prefix)?.is_empty());
+
+    // A failed close also leaves the destination untouched.
+    let writer = options
+        .mesh_type(ptex::MeshType::Triangle)
+        .open(&filename)?;
+    let non_square = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(1, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    assert!(writer
+        .write_face(0, &non_square, &vec![1_u8, 2], 0)
+        .is_err());
+    assert!(matches!(
+        writer.close(),
+        Err(ptex::Error::WriteFailed(_, _))
+    ));
+    assert_eq!(fs::read(&filename)?, original);
+    assert!(tmp_files_with_prefix(temp_prefix)?.is_empty());
+
+    // Without atomic mode Ptex replaces the destination even when the writer is dropped.
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2).open(&filename)?;
+    writer.write_face(0, &face_info, &vec![30_u8], 0)?;
+    writer.write_face(1, &face_info, &vec![40_u8], 0)?;
+    drop(writer);
+    assert_ne!(fs::read(&filename)?, original);
+    write_two_face_file(&filename, [10, 20])?;
+
+    // Closing replaces the destination.
+    let writer = options.open(&filename)?;
+    writer.write_face(0, &face_info, &vec![30_u8], 0)?;
+    writer.write_face(1, &face_info, &vec![40_u8], 0)?;
+    writer.close()?;
+    assert!(tmp_files_with_prefix(temp_prefix)?.is_empty());
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        assert_eq!(texture.face_data::<u8>(0)?, vec![30]);
+        assert_eq!(texture.face_data::<u8>(1)?, vec![40]);
+    }
+
+    // Atomic edits start from a copy of the existing file.
+    let writer = options.edit(&filename, false)?;
+    writer.write_face(1, &face_info, &vec![50_u8], 0)?;
+    writer.close()?;
+    assert!(tmp_files_with_prefix(temp_prefix)?.is_empty());
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        assert_eq!(texture.face_data::<u8>(0)?, vec![30]);
+        assert_eq!(texture.face_data::<u8>(1)?, vec![50]);
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_close_with_progress() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_close_with_progress.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    let face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(6, 6),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    let buf: Vec<f32> = (0..64 * 64).map(|i| i as f32).collect();
+    let options = ptex::WriterOptions::new(ptex::DataType::Float32, 1, 1).generate_mipmaps(true);
+
+    let writer = options.open(&filename)?;
+    writer.write_face(0, &face_info, &buf, 0)?;
+    let mut reports = Vec::new();
+    writer.close_with_progress(&ptex::CancellationToken::new(), |progress| {
+        reports.push(*progress)
+    })?;
+    let last = reports.last().expect("expected a final progress report");
+    assert_eq!(last.bytes_written, fs::metadata(&filename)?.len());
+    assert!(reports
+        .windows(2)
+        .all(|pair| pair[0].elapsed <= pair[1].elapsed));
+    fs::remove_file(&filename)?;
+
+    // Cancelling during the close returns without waiting for Ptex, and the temporary
+    // file is removed once Ptex has finished. The existing file is left untouched.
+    let temp_prefix = ".ptex_writer_close_with_progress.ptx.";
+    write_two_face_file(&filename, [10, 20])?;
+    let original = fs::read(&filename)?;
+    let cancel = ptex::CancellationToken::new();
+    let atomic_options = options.atomic(true);
+    let writer = atomic_options.open(&filename)?;
+    writer.write_face(0, &face_info, &buf, 0)?;
+    let mut num_reports = 0;
+    let result = writer.close_with_progress(&cancel, |_| {
+        num_reports += 1;
+        cancel.cancel();
+    });
+    assert_eq!(result, Err(ptex::Error::Cancelled(filename.clone())));
+    assert_eq!(num_reports, 1);
+    assert_eq!(fs::read(&filename)?, original);
+    wait_for_tmp_files_removed(temp_prefix)?;
+    assert_eq!(fs::read(&filename)?, original);
+
+    // Cancelling before closing reports no progress.
+    let writer = atomic_options.open(&filename)?;
+    writer.write_face(0, &face_info, &buf, 0)?;
+    assert_eq!(
+        writer.close_with_progress(&cancel, |_| panic!("unexpected progress report")),
+        Err(ptex::Error::Cancelled(filename.clone()))
+    );
+    wait_for_tmp_files_removed(temp_prefix)?;
+    assert_eq!(fs::read(&filename)?, original);
+
+    // Edits made in place ignore cancellation and are applied.
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, 2).edit(&filename, true)?;
+    let small_face_info = ptex::FaceInfo::from_res_and_adjacency(
+        ptex::Res::from_uv(0, 0),
+        &[-1, -1, -1, -1],
+        &[0, 0, 0, 0],
+        false,
+    );
+    writer.write_face(1, &small_face_info, &vec![30_u8], 0)?;
+    let mut num_reports = 0;
+    writer.close_with_progress(&cancel, |_| num_reports += 1)?;
+    assert!(num_reports > 0);
+    {
+        let cache = ptex::Cache::new(0, 0, false);
+        let texture = cache.get(&filename)?;
+        assert_eq!(texture.face_data::<u8>(0)?, vec![10]);
+        assert_eq!(texture.face_data::<u8>(1)?, vec![30]);
+    }
+    fs::remove_file(&filename)?;
+
+    // New files that did not exist before can be cancelled.
+    let writer = options.open(&filename)?;
+    writer.write_face(0, &face_info, &buf, 0)?;
+    assert_eq!(
+        writer.close_with_progress(&cancel, |_| panic!("unexpected progress report")),
+        Err(ptex::Error::Cancelled(filename.clone()))
+    );
+    let start = std::time::Instant::now();
+    while filename.exists() {
+        assert!(start.elapsed() < std::time::Duration::from_secs(30));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    Ok(())
+}
+
+/// Wait for the temporary files in tests/tmp that start with a prefix to be removed.
+fn wait_for_tmp_files_removed(prefix: &str) -> Result<()> {
+    let start = std::time::Instant::now();
+    while !tmp_files_with_prefix(prefix)?.is_empty() {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(30),
+            "temporary files were not removed"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_triangles() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_triangles.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    // A consistently-oriented tetrahedron.
+    let counts = [3, 3, 3, 3];
+    let indices = [0, 1, 2, 0, 3, 1, 1, 3, 2, 0, 2, 3];
+    let mesh = ptex::mesh::MeshAdjacency::from_triangles(&counts, &indices)?;
+    let res = ptex::Res::from_uv(2, 2);
+    let size = res.size();
+
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt16, 1, mesh.num_faces() as i32)
+        .mesh_type(ptex::MeshType::Triangle)
+        .open(&filename)?;
+    for face_id in 0..mesh.num_faces() {
+        let buf: Vec<u16> = (0..size).map(|i| (face_id * size + i) as u16).collect();
+        writer.write_face(face_id as i32, &mesh.face_info(face_id, res), &buf, 0)?;
+    }
+    writer.close()?;
+
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    assert_eq!(texture.mesh_type(), ptex::MeshType::Triangle);
+    assert_eq!(texture.num_faces(), 4);
+    for face_id in 0..mesh.num_faces() {
+        let face_info = texture.face_info(face_id as i32)?;
+        assert_eq!(face_info.resolution(), res);
+        for edge in 0..3 {
+            assert_eq!(
+                face_info.adjacent_face(edge),
+                mesh.adjacent_faces(face_id)[edge as usize]
+            );
+            assert_eq!(
+                face_info.adjacent_edge(edge),
+                mesh.adjacent_edges(face_id)[edge as usize]
+            );
+        }
+
+        // Ptex's point filter samples each sub-triangle from the texel given by the
+        // documented layout.
+        let mut filter =
+            ptex::Filter::new(&texture, &ptex::FilterOptions::new(ptex::FilterType::Point))?;
+        let mut result = [0.0_f32];
+        for v in 0..res.v() {
+            for u in 0..res.u() {
+                let (bu, bv) = ptex::mesh::texel_to_barycentric(res, u, v).unwrap();
+                assert_eq!(ptex::mesh::barycentric_to_texel(res, bu, bv), (u, v));
+                filter.eval(
+                    &mut result,
+                    0,
+                    1,
+                    face_id as i32,
+                    bu,
+                    bv,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    0.0,
+                )?;
+                let index = (v * res.u() + u) as usize;
+                let expected = (face_id * size + index) as f32 / u16::MAX as f32;
+                assert!(
+                    (result[0] - expected).abs() < 1e-6,
+                    "face {face_id} texel ({u}, {v}): {} != {expected}",
+                    result[0]
+                );
+            }
+        }
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn ptex_writer_subfaces() -> Result<()> {
+    let filename = std::path::PathBuf::from("tests/tmp/ptex_writer_subfaces.ptx");
+    if filename.exists() {
+        fs::remove_file(&filename)?;
+    }
+    // A quad next to a pentagon, which is split into five subfaces.
+    let counts = [4, 5];
+    let indices = [0, 1, 2, 3, 1, 4, 5, 6, 2];
+    let mesh = ptex::mesh::MeshAdjacency::from_polygons(&counts, &indices)?;
+    assert_eq!(mesh.num_faces(), 6);
+
+    let writer = ptex::WriterOptions::new(ptex::DataType::UInt8, 1, mesh.num_faces() as i32)
+        .open(&filename)?;
+    for face_id in 0..mesh.num_faces() {
+        // Subfaces have half the resolution of regular faces.
+        let res = if mesh.is_subface(face_id) {
+            ptex::Res::from_uv(1, 1)
+        } else {
+            ptex::Res::from_uv(2, 2)
+        };
+        let buf = vec![face_id as u8; res.size()];
+        writer.write_face(face_id as i32, &mesh.face_info(face_id, res), &buf, 0)?;
+    }
+    writer.close()?;
+
+    let cache = ptex::Cache::new(0, 0, false);
+    let texture = cache.get(&filename)?;
+    assert_eq!(texture.num_faces(), 6);
+    for face_id in 0..mesh.num_faces() {
+        let face_info = texture.face_info(face_id as i32)?;
+        assert_eq!(face_info.is_subface(), mesh.is_subface(face_id));
+        for edge in 0..4 {
+            assert_eq!(
+                face_info.adjacent_face(edge),
+                mesh.adjacent_faces(face_id)[edge as usize]
+            );
+        }
+    }
+    for corner in 0..5 {
+        let face_id = mesh.face_id(1, corner).unwrap();
+        assert_eq!(face_id, 1 + corner as i32);
+        assert_eq!(texture.face_data::<u8>(face_id)?[0], face_id as u8);
+    }
+    fs::remove_file(&filename)?;
+
+    Ok(())
+}